@@ -0,0 +1,82 @@
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::schematic::{row_totals, Config, Row};
+
+/// Splits the schematic into bands of `band_height` rows which are summed in parallel
+///
+/// Every band also parses the rows bordering it so numbers and gears on its edges see all of
+/// their neighbours, but only counts what lies on its own rows.
+pub fn par_part12(input: &str, config: Config, band_height: usize) -> (usize, usize) {
+    assert_ne!(band_height, 0, "Bands need to be at least one row tall");
+
+    let lines = input.lines().collect::<Vec<_>>();
+    let radius = config.neighbourhood.radius();
+
+    (0..lines.len().div_ceil(band_height))
+        .into_par_iter()
+        .map(|band| {
+            let start = band * band_height;
+            let end = (start + band_height).min(lines.len());
+
+            let overlap_start = start.saturating_sub(radius);
+            let overlap_end = (end + radius).min(lines.len());
+
+            let rows = (overlap_start..overlap_end)
+                .map(|i| Row::parse(lines[i], i, &config))
+                .collect::<Vec<_>>();
+
+            (start..end)
+                .map(|i| {
+                    let row = &rows[i - overlap_start];
+                    let window_start = i.saturating_sub(radius).max(overlap_start);
+                    let window_end = (i + radius + 1).min(overlap_end);
+
+                    row_totals(
+                        &rows[window_start - overlap_start..window_end - overlap_start],
+                        row,
+                        &config,
+                    )
+                })
+                .fold((0, 0), |(x1, y1), (x2, y2)| (x1 + x2, y1 + y2))
+        })
+        .reduce(|| (0, 0), |(x1, y1), (x2, y2)| (x1 + x2, y1 + y2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{
+        part12_with,
+        tests::{configs, EDGES, FAR, SAMPLE},
+    };
+
+    #[test]
+    fn matches_whole_schematic_for_every_band_height() {
+        for input in [SAMPLE, EDGES, FAR] {
+            let rows = input.lines().count();
+
+            for config in configs() {
+                for band_height in 1..=rows + 1 {
+                    assert_eq!(
+                        par_part12(input, config, band_height),
+                        part12_with(input, config),
+                        "{:?} with bands of {band_height} rows",
+                        config.neighbourhood
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_whole_schematic_on_input() {
+        for config in configs() {
+            for band_height in [1, 7, 16, 140] {
+                assert_eq!(
+                    par_part12(crate::INPUT, config, band_height),
+                    part12_with(crate::INPUT, config)
+                );
+            }
+        }
+    }
+}
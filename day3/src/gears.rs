@@ -0,0 +1,178 @@
+use crate::schematic::{Neighbourhood, PartNumber, Row, Schematic, Symbol};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    pub fn matches(&self, n: usize) -> bool {
+        match *self {
+            Arity::Exactly(arity) => n == arity,
+            Arity::AtLeast(arity) => n >= arity,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Reducer {
+    Product,
+    Sum,
+    Max,
+    Custom(fn(&[usize]) -> usize),
+}
+
+impl Reducer {
+    pub fn reduce(&self, values: &[usize]) -> usize {
+        match self {
+            Reducer::Product => values.iter().product(),
+            Reducer::Sum => values.iter().sum(),
+            Reducer::Max => values.iter().copied().max().unwrap_or(0),
+            Reducer::Custom(f) => f(values),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GearQuery {
+    pub symbol: u8,
+    pub arity: Arity,
+    pub reducer: Reducer,
+}
+
+impl GearQuery {
+    /// The gear at `symbol`, if it matches this query, with its numbers taken from `window`
+    pub fn gear<'a>(
+        &self,
+        symbol: Symbol,
+        window: &'a [Row],
+        neighbourhood: Neighbourhood,
+    ) -> Option<Gear<'a>> {
+        if symbol.symbol != self.symbol {
+            return None;
+        }
+
+        let numbers = neighbourhood
+            .adjacent_numbers(symbol, window)
+            .collect::<Vec<_>>();
+
+        self.arity
+            .matches(numbers.len())
+            .then_some(Gear { symbol, numbers })
+    }
+}
+
+impl Default for GearQuery {
+    fn default() -> Self {
+        Self {
+            symbol: b'*',
+            arity: Arity::Exactly(2),
+            reducer: Reducer::Product,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Gear<'a> {
+    pub symbol: Symbol,
+    pub numbers: Vec<&'a PartNumber>,
+}
+
+impl Gear<'_> {
+    pub fn values(&self) -> Vec<usize> {
+        self.numbers.iter().map(|number| number.value).collect()
+    }
+
+    pub fn ratio(&self, reducer: Reducer) -> usize {
+        reducer.reduce(&self.values())
+    }
+}
+
+impl Schematic {
+    pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.rows.iter().flat_map(|row| &row.symbols)
+    }
+
+    pub fn symbol_at(&self, row: usize, column: usize) -> Option<Symbol> {
+        self.rows
+            .get(row)?
+            .symbols
+            .iter()
+            .find(|symbol| symbol.column == column)
+            .copied()
+    }
+
+    pub fn gears(&self, query: GearQuery) -> impl Iterator<Item = Gear<'_>> {
+        self.symbols().filter_map(move |&symbol| {
+            query.gear(symbol, self.window(symbol.row), self.config.neighbourhood)
+        })
+    }
+
+    pub fn gear_ratio_sum(&self, query: GearQuery) -> usize {
+        self.gears(query)
+            .map(|gear| gear.ratio(query.reducer))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{tests::SAMPLE, Config};
+
+    fn sum(input: &str, symbol: u8, arity: Arity, reducer: Reducer) -> usize {
+        Schematic::parse(input, Config::default()).gear_ratio_sum(GearQuery {
+            symbol,
+            arity,
+            reducer,
+        })
+    }
+
+    #[test]
+    fn sample_queries() {
+        assert_eq!(
+            sum(SAMPLE, b'*', Arity::Exactly(2), Reducer::Product),
+            467835
+        );
+        assert_eq!(sum(SAMPLE, b'*', Arity::Exactly(1), Reducer::Product), 617);
+        assert_eq!(
+            sum(SAMPLE, b'*', Arity::AtLeast(1), Reducer::Sum),
+            467 + 35 + 617 + 755 + 598
+        );
+        assert_eq!(
+            sum(SAMPLE, b'*', Arity::AtLeast(2), Reducer::Max),
+            467 + 755
+        );
+        assert_eq!(
+            sum(
+                SAMPLE,
+                b'*',
+                Arity::AtLeast(2),
+                Reducer::Custom(|values| values.iter().copied().min().unwrap())
+            ),
+            35 + 598
+        );
+        assert_eq!(sum(SAMPLE, b'#', Arity::Exactly(1), Reducer::Sum), 633);
+        assert_eq!(sum(SAMPLE, b'*', Arity::Exactly(3), Reducer::Product), 0);
+    }
+
+    #[test]
+    fn at_least_zero_counts_lonely_symbols() {
+        let input = "*...\n....\n.5*.";
+
+        // The lonely gear is the empty product, 1
+        assert_eq!(sum(input, b'*', Arity::AtLeast(0), Reducer::Product), 1 + 5);
+        assert_eq!(sum(input, b'*', Arity::AtLeast(0), Reducer::Sum), 5);
+        assert_eq!(sum(input, b'*', Arity::Exactly(0), Reducer::Max), 0);
+
+        let gears = Schematic::parse(input, Config::default())
+            .gears(GearQuery {
+                arity: Arity::AtLeast(0),
+                ..GearQuery::default()
+            })
+            .map(|gear| gear.values())
+            .collect::<Vec<_>>();
+        assert_eq!(gears, [vec![], vec![5]]);
+    }
+}
@@ -0,0 +1,165 @@
+use std::{collections::HashMap, fmt::Write};
+
+use serde::Serialize;
+
+use crate::schematic::Schematic;
+
+/// Bipartite graph of numbers and the symbols they are adjacent to
+#[derive(Debug, Serialize)]
+pub struct Graph {
+    pub numbers: Vec<NumberNode>,
+    pub symbols: Vec<SymbolNode>,
+    pub edges: Vec<Edge>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NumberNode {
+    pub id: usize,
+    pub value: usize,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SymbolNode {
+    pub id: usize,
+    pub symbol: char,
+    pub row: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Edge {
+    pub number: usize,
+    pub symbol: usize,
+}
+
+impl Graph {
+    pub fn new(schematic: &Schematic) -> Self {
+        let symbols: Vec<SymbolNode> = schematic
+            .symbols()
+            .enumerate()
+            .map(|(id, symbol)| SymbolNode {
+                id,
+                symbol: symbol.symbol as char,
+                row: symbol.row,
+                column: symbol.column,
+            })
+            .collect();
+
+        let symbol_ids: HashMap<(usize, usize), usize> = symbols
+            .iter()
+            .map(|node| ((node.row, node.column), node.id))
+            .collect();
+
+        let mut numbers = Vec::new();
+        let mut edges = Vec::new();
+
+        for (id, number) in schematic
+            .rows
+            .iter()
+            .flat_map(|row| &row.numbers)
+            .enumerate()
+        {
+            numbers.push(NumberNode {
+                id,
+                value: number.value,
+                row: number.row,
+                start: number.columns.start,
+                end: number.columns.end,
+            });
+
+            edges.extend(
+                schematic
+                    .window(number.row)
+                    .iter()
+                    .flat_map(|row| &row.symbols)
+                    .filter(|symbol| schematic.config.neighbourhood.touches(number, symbol))
+                    .map(|symbol| Edge {
+                        number: id,
+                        symbol: symbol_ids[&(symbol.row, symbol.column)],
+                    }),
+            );
+        }
+
+        Self {
+            numbers,
+            symbols,
+            edges,
+        }
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph schematic {\n");
+
+        for number in &self.numbers {
+            writeln!(
+                dot,
+                "    n{} [shape=box, label=\"{}\\n({}, {}..{})\"];",
+                number.id, number.value, number.row, number.start, number.end
+            )
+            .unwrap();
+        }
+
+        for symbol in &self.symbols {
+            let label = match symbol.symbol {
+                c @ ('"' | '\\') => format!("\\{c}"),
+                c => c.to_string(),
+            };
+
+            writeln!(
+                dot,
+                "    s{} [shape=circle, label=\"{label}\\n({}, {})\"];",
+                symbol.id, symbol.row, symbol.column
+            )
+            .unwrap();
+        }
+
+        for edge in &self.edges {
+            writeln!(dot, "    n{} -- s{};", edge.number, edge.symbol).unwrap();
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{tests::SAMPLE, Config};
+
+    #[test]
+    fn sample() {
+        let graph = Graph::new(&Schematic::parse(SAMPLE, Config::default()));
+
+        assert_eq!(graph.numbers.len(), 10);
+        assert_eq!(graph.symbols.len(), 6);
+        // Every number but 114 and 58 touches one symbol
+        assert_eq!(graph.edges.len(), 8);
+
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+        assert_eq!(json["edges"].as_array().unwrap().len(), 8);
+    }
+
+    #[test]
+    fn dot_escapes_labels() {
+        let dot = Graph::new(&Schematic::parse("\"1\\", Config::default())).to_dot();
+
+        assert_eq!(
+            dot,
+            "graph schematic {\n    \
+             n0 [shape=box, label=\"1\\n(0, 1..2)\"];\n    \
+             s0 [shape=circle, label=\"\\\"\\n(0, 0)\"];\n    \
+             s1 [shape=circle, label=\"\\\\\\n(0, 2)\"];\n    \
+             n0 -- s0;\n    \
+             n0 -- s1;\n\
+             }\n"
+        );
+    }
+}
@@ -1,192 +1,106 @@
+pub mod bands;
+pub mod gears;
+pub mod graph;
+pub mod render;
+pub mod schematic;
+pub mod streaming;
+
 use core::panic;
-use std::{collections::BTreeMap, mem, num::IntErrorKind};
+use std::env;
+
+use graph::Graph;
+use render::{render, RenderOptions, Window};
+use schematic::{part12_with, Config, Neighbourhood, Schematic};
 
 pub static INPUT: &str = include_str!("input.txt");
 
 fn main() {
-    let (part1, part2) = part12(INPUT);
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    match args.as_slice() {
+        ["dot"] => {
+            let schematic = Schematic::parse(INPUT, Config::default());
+            print!("{}", Graph::new(&schematic).to_dot());
+            return;
+        }
+        ["json"] => {
+            let schematic = Schematic::parse(INPUT, Config::default());
+            println!("{}", Graph::new(&schematic).to_json().unwrap());
+            return;
+        }
+        ["render", window @ ..] => {
+            let window = match window {
+                [] => None,
+                &[row, column] => Some((row, column, "5")),
+                &[row, column, radius] => Some((row, column, radius)),
+                _ => panic!("Usage: render [row column [radius]]"),
+            }
+            .map(|(row, column, radius)| Window {
+                row: row.parse().unwrap(),
+                column: column.parse().unwrap(),
+                radius: radius.parse().unwrap(),
+            });
+
+            let schematic = Schematic::parse(INPUT, Config::default());
+            let options = RenderOptions {
+                window,
+                ..Default::default()
+            };
+            print!("{}", render(&schematic, options));
+            return;
+        }
+        _ => {}
+    }
+
+    let config = parse_config(&args).expect(
+        "Usage: [four|eight|chebyshev=K] [empty=C] [symbols=punctuation|any], or dot, json, render",
+    );
+    let (part1, part2) = part12_with(INPUT, config);
 
     println!("{part1}");
     println!("{part2}");
 }
 
-pub fn part12(input: &str) -> (usize, usize) {
-    let mut lines = input.lines();
-
-    let mut sum = 0;
-    let mut gearbox_sum = 0;
-
-    // Assuming first lines has no symbols
-    let mut prev_line = lines.next().unwrap();
-
-    let mut prev_line_symbols = Vec::<(usize, u8)>::new();
-    let mut current_line_symbols = Vec::<(usize, u8)>::new();
-
-    let mut prev_possible_gearsets = BTreeMap::<usize, Vec<usize>>::new();
-    let mut current_possible_gearsets = BTreeMap::<usize, Vec<usize>>::new();
-
-    for line in lines {
-        let mut skip = 0;
-
-        for (i, &c) in line.as_bytes().iter().enumerate() {
-            if skip != 0 {
-                skip -= 1;
-                continue;
-            }
-
-            if c.is_ascii_punctuation() && c != b'.' {
-                let (top_first, top_last) = parse_top_line(prev_line, i);
-
-                sum += top_first.unwrap_or(0);
-                sum += top_last.unwrap_or(0);
-
-                current_line_symbols.push((i, c));
-
-                let left_num = parse_3_digits_rev(line.split_at(i).0);
-
-                sum += left_num.map_or(0, |(n, _)| n);
-
-                let right_num = parse_3_digits(line.split_at(i + 1).1);
-
-                skip = right_num.map_or(0, |(_, s)| s);
-
-                sum += right_num.map_or(0, |(n, _)| n);
-
-                if c == b'*' {
-                    let gearsets: Vec<usize> = [
-                        top_first,
-                        top_last,
-                        left_num.map(|(n, _)| n),
-                        right_num.map(|(n, _)| n),
-                    ]
-                    .into_iter()
-                    .flatten()
-                    .collect();
+/// Options for the default run, e.g. `chebyshev=2 empty=_ symbols=any`
+fn parse_config(options: &[&str]) -> Option<Config> {
+    let mut config = Config::default();
 
-                    if gearsets.len() <= 2 {
-                        current_possible_gearsets.insert(i, gearsets);
-                    }
-                }
-            }
-
-            if c.is_ascii_digit() {
-                if let Some(&(idx, c)) = prev_line_symbols
-                    .iter()
-                    .find(|(p_i, _)| (p_i - 1..=p_i + 1).contains(&i))
-                {
-                    let (num, skip_next) = parse_free_num(line, i);
-
-                    skip = skip_next;
-
-                    if c == b'*' {
-                        prev_possible_gearsets.entry(idx).or_default().push(num);
-                    }
-
-                    sum += num;
-                }
+    for &option in options {
+        match option.split_once('=') {
+            None if option == "four" => config.neighbourhood = Neighbourhood::FourConnected,
+            None if option == "eight" => config.neighbourhood = Neighbourhood::EightConnected,
+            Some(("chebyshev", radius)) => {
+                config.neighbourhood = Neighbourhood::Chebyshev(radius.parse().ok()?)
             }
+            Some(("empty", empty)) => config.empty = *empty.as_bytes().first()?,
+            Some(("symbols", "punctuation")) => config.is_symbol = |c| c.is_ascii_punctuation(),
+            // Anything which isn't a digit or empty
+            Some(("symbols", "any")) => config.is_symbol = |_| true,
+            _ => return None,
         }
-
-        prev_line_symbols.clear();
-        mem::swap(&mut prev_line_symbols, &mut current_line_symbols);
-
-        gearbox_sum += prev_possible_gearsets
-            .values()
-            .filter_map(|x| x.as_slice().try_into().ok())
-            .map(|[x, y]: [usize; 2]| x * y)
-            .sum::<usize>();
-
-        prev_possible_gearsets.clear();
-
-        mem::swap(&mut prev_possible_gearsets, &mut current_possible_gearsets);
-
-        prev_line = line;
     }
 
-    (sum, gearbox_sum)
+    Some(config)
 }
 
-fn parse_top_line(prev_line: &str, i: usize) -> (Option<usize>, Option<usize>) {
-    let combined: [u8; 3] = prev_line.as_bytes()[i - 1..=i + 1].try_into().unwrap();
-    let nice = combined.map(|x| x.is_ascii_digit());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    match nice {
-        [true, false, true] => {
-            let (left, right) = prev_line.split_at(i);
-            (
-                parse_3_digits_rev(left).map(|x| x.0),
-                parse_3_digits(&right[1..]).map(|x| x.0),
-            )
-        }
-        [true, true, true] => (
-            Some(
-                unsafe { std::str::from_utf8_unchecked(&combined) }
-                    .parse()
-                    .unwrap(),
-            ),
-            None,
-        ),
+    #[test]
+    fn config_options() {
+        let config = parse_config(&["chebyshev=3", "empty=_", "symbols=any"]).unwrap();
 
-        [false, true, false] => (Some((combined[1] - b'0') as usize), None),
-        [false, false, false] => (None, None),
+        assert_eq!(config.neighbourhood, Neighbourhood::Chebyshev(3));
+        assert_eq!(config.empty, b'_');
+        assert!(config.symbol(b'a') && !config.symbol(b'_') && !config.symbol(b'7'));
 
-        [false, middle, true] => (
-            None,
-            parse_3_digits(prev_line.split_at(i + usize::from(!middle)).1).map(|x| x.0),
-        ),
-        [true, middle, false] => (
-            parse_3_digits_rev(prev_line.split_at(i + usize::from(middle)).0).map(|x| x.0),
-            None,
-        ),
+        assert_eq!(
+            parse_config(&["four"]).unwrap().neighbourhood,
+            Neighbourhood::FourConnected
+        );
+        assert!(parse_config(&["chebyshev=x"]).is_none());
+        assert!(parse_config(&["hexagonal"]).is_none());
     }
 }
-
-fn parse_free_num(prev_line: &str, i: usize) -> (usize, usize) {
-    let bytes = prev_line.as_bytes();
-    match [bytes[i - 1].is_ascii_digit(), bytes[i + 1].is_ascii_digit()] {
-        [true, true] => (
-            unsafe { std::str::from_utf8_unchecked(&bytes[i - 1..=i + 1]) }
-                .parse()
-                .unwrap(),
-            1,
-        ),
-        [true, false] => {
-            let (n, _) = parse_3_digits_rev(prev_line.split_at(i + 1).0).unwrap();
-            (n, 0)
-        }
-        [false, true] => {
-            let (n, len) = parse_3_digits(prev_line.split_at(i).1).unwrap();
-
-            (n, len - 1)
-        }
-        [false, false] => ((bytes[i] - b'0') as usize, 0),
-    }
-}
-
-fn parse_3_digits_rev(string: &str) -> Option<(usize, usize)> {
-    let possible_num = string
-        .rsplit_once(|c: char| !c.is_ascii_digit())
-        .map(|(_, x)| x)
-        .unwrap_or(string);
-    let len = possible_num.len();
-
-    return match possible_num.parse() {
-        Ok(n) => Some((n, len)),
-        Err(e) if e.kind() == &IntErrorKind::Empty => None,
-        Err(e) => panic!("{e}"),
-    };
-}
-
-fn parse_3_digits(string: &str) -> Option<(usize, usize)> {
-    let possible_num = string
-        .split_once(|c: char| !c.is_ascii_digit())
-        .map(|(x, _)| x)
-        .unwrap_or(string);
-    let len = possible_num.len();
-
-    return match possible_num.parse() {
-        Ok(n) => Some((n, len)),
-        Err(e) if e.kind() == &IntErrorKind::Empty => None,
-        Err(e) => panic!("{e}"),
-    };
-}
@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+
+use crate::{gears::GearQuery, schematic::Schematic};
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Counted,
+    Uncounted,
+    Symbol,
+    Gear,
+}
+
+impl Style {
+    fn code(&self) -> &'static str {
+        match self {
+            Style::Plain => RESET,
+            Style::Counted => "\x1b[1;32m",
+            Style::Uncounted => "\x1b[2m",
+            Style::Symbol => "\x1b[1;36m",
+            Style::Gear => "\x1b[1;33m",
+        }
+    }
+}
+
+/// Part of the schematic to print, `radius` rows and columns around the center
+#[derive(Debug, Clone, Copy)]
+pub struct Window {
+    pub row: usize,
+    pub column: usize,
+    pub radius: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions {
+    pub gears: GearQuery,
+    pub window: Option<Window>,
+}
+
+pub fn render(schematic: &Schematic, options: RenderOptions) -> String {
+    let gears = schematic.gears(options.gears).collect::<Vec<_>>();
+
+    let gear_symbols: HashSet<(usize, usize)> = gears
+        .iter()
+        .map(|gear| (gear.symbol.row, gear.symbol.column))
+        .collect();
+    let gear_numbers: HashSet<(usize, usize)> = gears
+        .iter()
+        .flat_map(|gear| &gear.numbers)
+        .map(|number| (number.row, number.columns.start))
+        .collect();
+
+    let (rows, columns) = match options.window {
+        Some(Window {
+            row,
+            column,
+            radius,
+        }) => (
+            row.saturating_sub(radius)..row + radius + 1,
+            column.saturating_sub(radius)..column + radius + 1,
+        ),
+        None => (0..usize::MAX, 0..usize::MAX),
+    };
+
+    let mut rendered = String::new();
+
+    for (i, (line, row)) in schematic.source.lines().zip(&schematic.rows).enumerate() {
+        if !rows.contains(&i) {
+            continue;
+        }
+
+        let mut styles = vec![Style::Plain; line.len()];
+
+        for number in &row.numbers {
+            let style = if gear_numbers.contains(&(number.row, number.columns.start)) {
+                Style::Gear
+            } else if schematic.is_part_number(number) {
+                Style::Counted
+            } else {
+                Style::Uncounted
+            };
+
+            styles[number.columns.clone()].fill(style);
+        }
+
+        for symbol in &row.symbols {
+            styles[symbol.column] = if gear_symbols.contains(&(symbol.row, symbol.column)) {
+                Style::Gear
+            } else {
+                Style::Symbol
+            };
+        }
+
+        let mut current = Style::Plain;
+
+        for (j, c) in line.char_indices() {
+            if !columns.contains(&j) {
+                continue;
+            }
+
+            if styles[j] != current {
+                current = styles[j];
+                rendered.push_str(current.code());
+            }
+
+            rendered.push(c);
+        }
+
+        if current != Style::Plain {
+            rendered.push_str(RESET);
+        }
+
+        rendered.push('\n');
+    }
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::Config;
+
+    const INPUT: &str = "\
+12.3*4
+.5....
+..#...
+.....6";
+
+    /// Writes every run of text after the code of its style
+    fn styled(runs: &[(Style, &str)]) -> String {
+        runs.iter()
+            .map(|(style, text)| format!("{}{text}", style.code()))
+            .collect()
+    }
+
+    #[test]
+    fn styles() {
+        use Style::*;
+
+        let rendered = render(
+            &Schematic::parse(INPUT, Config::default()),
+            RenderOptions::default(),
+        );
+        let lines = rendered.lines().collect::<Vec<_>>();
+
+        // 12 and 6 touch nothing, 3 and 4 are the gear's numbers, 5 is only next to `#`
+        assert_eq!(
+            lines,
+            [
+                styled(&[(Uncounted, "12"), (Plain, "."), (Gear, "3*4"), (Plain, "")]),
+                format!(".{}", styled(&[(Counted, "5"), (Plain, "....")])),
+                format!("..{}", styled(&[(Symbol, "#"), (Plain, "...")])),
+                format!(".....{}", styled(&[(Uncounted, "6"), (Plain, "")])),
+            ]
+        );
+    }
+
+    #[test]
+    fn window_clips_rows_and_columns() {
+        let options = RenderOptions {
+            window: Some(Window {
+                row: 0,
+                column: 4,
+                radius: 1,
+            }),
+            ..Default::default()
+        };
+        let rendered = render(&Schematic::parse(INPUT, Config::default()), options);
+
+        assert_eq!(
+            rendered,
+            format!(
+                "{}\n...\n",
+                styled(&[(Style::Gear, "3*4"), (Style::Plain, "")])
+            )
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use std::ops::Range;
+
+use crate::gears::GearQuery;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    pub is_symbol: fn(u8) -> bool,
+    pub empty: u8,
+    pub neighbourhood: Neighbourhood,
+}
+
+impl Config {
+    pub fn symbol(&self, c: u8) -> bool {
+        c != self.empty && !c.is_ascii_digit() && (self.is_symbol)(c)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            is_symbol: |c| c.is_ascii_punctuation(),
+            empty: b'.',
+            neighbourhood: Neighbourhood::EightConnected,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    FourConnected,
+    EightConnected,
+    Chebyshev(usize),
+}
+
+impl Neighbourhood {
+    /// How many rows above and below a cell can still be adjacent to it
+    pub fn radius(&self) -> usize {
+        match self {
+            Neighbourhood::FourConnected | Neighbourhood::EightConnected => 1,
+            &Neighbourhood::Chebyshev(k) => k,
+        }
+    }
+
+    pub fn touches(&self, number: &PartNumber, symbol: &Symbol) -> bool {
+        let rows = number.row.abs_diff(symbol.row);
+        let columns = if symbol.column < number.columns.start {
+            number.columns.start - symbol.column
+        } else if symbol.column >= number.columns.end {
+            symbol.column - (number.columns.end - 1)
+        } else {
+            0
+        };
+
+        match self {
+            Neighbourhood::FourConnected => rows + columns == 1,
+            Neighbourhood::EightConnected => rows <= 1 && columns <= 1,
+            &Neighbourhood::Chebyshev(k) => rows <= k && columns <= k,
+        }
+    }
+
+    /// Whether `number` touches a symbol in any of the `window` rows
+    pub fn touches_symbol(&self, number: &PartNumber, window: &[Row]) -> bool {
+        window
+            .iter()
+            .flat_map(|row| &row.symbols)
+            .any(|symbol| self.touches(number, symbol))
+    }
+
+    /// Numbers in the `window` rows which touch `symbol`
+    pub fn adjacent_numbers(
+        self,
+        symbol: Symbol,
+        window: &[Row],
+    ) -> impl Iterator<Item = &PartNumber> {
+        window
+            .iter()
+            .flat_map(|row| &row.numbers)
+            .filter(move |number| self.touches(number, &symbol))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    pub value: usize,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub symbol: u8,
+    pub row: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Row {
+    pub numbers: Vec<PartNumber>,
+    pub symbols: Vec<Symbol>,
+}
+
+impl Row {
+    pub fn parse(line: &str, row: usize, config: &Config) -> Self {
+        let bytes = line.as_bytes();
+        let mut parsed = Row::default();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let c = bytes[i];
+
+            if c.is_ascii_digit() {
+                let start = i;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+
+                parsed.numbers.push(PartNumber {
+                    value: line[start..i].parse().unwrap(),
+                    row,
+                    columns: start..i,
+                });
+
+                continue;
+            }
+
+            if config.symbol(c) {
+                parsed.symbols.push(Symbol {
+                    symbol: c,
+                    row,
+                    column: i,
+                });
+            }
+
+            i += 1;
+        }
+
+        parsed
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub rows: Vec<Row>,
+    pub config: Config,
+    /// Text the rows were parsed from, so they can be shown with it
+    pub source: String,
+}
+
+impl Schematic {
+    pub fn parse(input: &str, config: Config) -> Self {
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(row, line)| Row::parse(line, row, &config))
+            .collect();
+
+        Self {
+            rows,
+            config,
+            source: input.to_owned(),
+        }
+    }
+
+    /// Rows which can hold cells adjacent to something on `row`
+    pub fn window(&self, row: usize) -> &[Row] {
+        let radius = self.config.neighbourhood.radius();
+        let start = row.saturating_sub(radius);
+        let end = (row + radius + 1).min(self.rows.len());
+
+        &self.rows[start..end]
+    }
+
+    pub fn is_part_number(&self, number: &PartNumber) -> bool {
+        self.config
+            .neighbourhood
+            .touches_symbol(number, self.window(number.row))
+    }
+
+    pub fn part_numbers(&self) -> impl Iterator<Item = &PartNumber> {
+        self.rows
+            .iter()
+            .flat_map(|row| &row.numbers)
+            .filter(|number| self.is_part_number(number))
+    }
+
+    pub fn adjacent_numbers(&self, symbol: Symbol) -> impl Iterator<Item = &PartNumber> {
+        self.config
+            .neighbourhood
+            .adjacent_numbers(symbol, self.window(symbol.row))
+    }
+
+    pub fn part1(&self) -> usize {
+        self.part_numbers().map(|number| number.value).sum()
+    }
+
+    pub fn part2(&self) -> usize {
+        self.gear_ratio_sum(GearQuery::default())
+    }
+}
+
+pub fn part12_with(input: &str, config: Config) -> (usize, usize) {
+    let schematic = Schematic::parse(input, config);
+
+    (schematic.part1(), schematic.part2())
+}
+
+/// Part 1 and 2 totals of everything on `row`, with `window` holding its neighbouring rows
+pub fn row_totals(window: &[Row], row: &Row, config: &Config) -> (usize, usize) {
+    let query = GearQuery::default();
+
+    let part1 = row
+        .numbers
+        .iter()
+        .filter(|number| config.neighbourhood.touches_symbol(number, window))
+        .map(|number| number.value)
+        .sum();
+
+    let part2 = row
+        .symbols
+        .iter()
+        .filter_map(|&symbol| query.gear(symbol, window, config.neighbourhood))
+        .map(|gear| gear.ratio(query.reducer))
+        .sum();
+
+    (part1, part2)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const SAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    /// Symbols and numbers on the first and last rows and columns
+    pub(crate) const EDGES: &str = "\
+*12..#7
+..3....
+45.*..6
+.......
+7...9$.
+1*2..*3";
+
+    /// Numbers two to four rows and columns away from the symbols, only reached by `Chebyshev(k)` with `k > 1`
+    pub(crate) const FAR: &str = "\
+11.....22
+.........
+....*....
+.........
+33..#.7.4
+.........
+.........
+5...*...6";
+
+    pub(crate) fn configs() -> Vec<Config> {
+        [
+            Neighbourhood::FourConnected,
+            Neighbourhood::EightConnected,
+            Neighbourhood::Chebyshev(2),
+            Neighbourhood::Chebyshev(3),
+        ]
+        .into_iter()
+        .map(|neighbourhood| Config {
+            neighbourhood,
+            ..Config::default()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn sample() {
+        assert_eq!(part12_with(SAMPLE, Config::default()), (4361, 467835));
+    }
+
+    #[test]
+    fn input() {
+        assert_eq!(
+            part12_with(crate::INPUT, Config::default()),
+            (535351, 87287096)
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use std::{collections::VecDeque, io::BufRead};
+
+use crate::schematic::{row_totals, Config, Row};
+
+/// Same as [`crate::part12`] but only keeps the rows around the one being summed in memory,
+/// three of them for the default neighbourhood
+pub fn part12_readbuf(buf: impl BufRead, config: Config) -> std::io::Result<(usize, usize)> {
+    let radius = config.neighbourhood.radius();
+    let mut window = VecDeque::with_capacity(2 * radius + 1);
+
+    let mut sum = 0;
+    let mut gearbox_sum = 0;
+    let mut rows_read = 0;
+
+    let mut sum_row = |window: &mut VecDeque<Row>, position: usize| {
+        let window = window.make_contiguous();
+        let (part1, part2) = row_totals(window, &window[position], &config);
+
+        sum += part1;
+        gearbox_sum += part2;
+    };
+
+    for line in buf.lines() {
+        if window.len() == 2 * radius + 1 {
+            window.pop_front();
+        }
+
+        window.push_back(Row::parse(&line?, rows_read, &config));
+        rows_read += 1;
+
+        // The row `radius` lines above now has all of its neighbours loaded
+        if rows_read > radius {
+            let position = window.len() - radius - 1;
+            sum_row(&mut window, position);
+        }
+    }
+
+    // The last rows have no more neighbours coming below them
+    for position in window.len().saturating_sub(radius)..window.len() {
+        sum_row(&mut window, position);
+    }
+
+    Ok((sum, gearbox_sum))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::schematic::{
+        part12_with,
+        tests::{configs, EDGES, FAR, SAMPLE},
+    };
+
+    #[test]
+    fn matches_whole_schematic() {
+        for input in [SAMPLE, EDGES, FAR, crate::INPUT] {
+            for config in configs() {
+                assert_eq!(
+                    part12_readbuf(Cursor::new(input), config).unwrap(),
+                    part12_with(input, config),
+                    "{:?}",
+                    config.neighbourhood
+                );
+            }
+        }
+    }
+}
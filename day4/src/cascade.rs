@@ -0,0 +1,288 @@
+use std::{error::Error, fmt::Display};
+
+use crate::scratchcard::Scratchcard;
+
+/// What to do with copies of card ids which have no card, in a gap between ids or past the last card
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutOfRange {
+    /// Give the copies to the next card after the missing id instead, or to the last card when
+    /// the id is past it, unless it is the card winning them
+    Clamp,
+    #[default]
+    Ignore,
+    Error,
+}
+
+/// Part 2 rule, every card wins copies of the cards after it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyCascade {
+    /// Distance between the ids of the cards won, 1 for the next cards
+    pub stride: u32,
+    /// Most cards a single card can win copies of, regardless of its matches
+    pub cap: Option<u32>,
+    pub policy: OutOfRange,
+}
+
+impl Default for CopyCascade {
+    fn default() -> Self {
+        Self {
+            stride: 1,
+            cap: None,
+            policy: OutOfRange::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CascadeError {
+    DuplicateCard(u32),
+    /// `copy` is `None` when the id won is past `u32::MAX`
+    OutOfRange {
+        card: u32,
+        copy: Option<u32>,
+    },
+    TooManyCopies(u32),
+}
+
+impl Display for CascadeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CascadeError::DuplicateCard(id) => write!(f, "Card {id} appears more than once"),
+            CascadeError::OutOfRange {
+                card,
+                copy: Some(copy),
+            } => {
+                write!(
+                    f,
+                    "Card {card} wins a copy of card {copy} which does not exist"
+                )
+            }
+            CascadeError::OutOfRange { card, copy: None } => {
+                write!(f, "Card {card} wins a copy of a card past u32::MAX")
+            }
+            CascadeError::TooManyCopies(id) => {
+                write!(f, "Card {id} has more copies than fit in a usize")
+            }
+        }
+    }
+}
+
+impl Error for CascadeError {}
+
+#[derive(Debug, Clone)]
+pub struct Held<'a> {
+    pub card: &'a Scratchcard,
+    pub amount: usize,
+    /// Copies won from each earlier card, by card id
+    pub won_from: Vec<(u32, usize)>,
+}
+
+/// Amount of every card held once all copies have been won, ordered by card id
+///
+/// Cards can be given in any order and ids can have gaps, copies of ids without a card follow the
+/// rule's [`OutOfRange`] policy.
+pub fn cascade(cards: &[Scratchcard], rule: CopyCascade) -> Result<Vec<Held<'_>>, CascadeError> {
+    let mut held = cards
+        .iter()
+        .map(|card| Held {
+            card,
+            amount: 1,
+            won_from: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    held.sort_unstable_by_key(|held| held.card.id);
+
+    if let Some(pair) = held
+        .windows(2)
+        .find(|pair| pair[0].card.id == pair[1].card.id)
+    {
+        return Err(CascadeError::DuplicateCard(pair[0].card.id));
+    }
+
+    let Some(last) = held.last().map(|held| held.card.id) else {
+        return Ok(held);
+    };
+
+    for i in 0..held.len() {
+        let Held { card, amount, .. } = held[i];
+
+        let won = rule
+            .cap
+            .map_or(card.matches(), |cap| card.matches().min(cap));
+
+        let copies = (1..=won).map(|n| {
+            n.checked_mul(rule.stride)
+                .and_then(|offset| card.id.checked_add(offset))
+        });
+
+        for copy in copies {
+            // Only later cards can be won so there is no need to search the ones before, ids
+            // which don't fit in a `u32` are past the last card too
+            let found = match copy {
+                Some(copy) => held[i + 1..].binary_search_by_key(&copy, |held| held.card.id),
+                None => Err(held.len() - i - 1),
+            };
+
+            // `next` is the first card after the missing id, if there is one
+            let j = match (found, rule.policy) {
+                (Ok(j), _) => i + 1 + j,
+                (Err(next), OutOfRange::Clamp) if i + 1 + next < held.len() => i + 1 + next,
+                (Err(_), OutOfRange::Clamp) if card.id != last => held.len() - 1,
+                (Err(_), OutOfRange::Clamp) => break,
+                (Err(_), OutOfRange::Ignore) => continue,
+                (Err(_), OutOfRange::Error) => {
+                    return Err(CascadeError::OutOfRange {
+                        card: card.id,
+                        copy,
+                    })
+                }
+            };
+
+            let copy = held[j].card.id;
+            let won = &mut held[j];
+            won.amount = won
+                .amount
+                .checked_add(amount)
+                .ok_or(CascadeError::TooManyCopies(copy))?;
+
+            match won.won_from.last_mut() {
+                Some((from, copies)) if *from == card.id => *copies += amount,
+                _ => won.won_from.push((card.id, amount)),
+            }
+        }
+    }
+
+    Ok(held)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(input: &str) -> Vec<Scratchcard> {
+        input.lines().map(|line| line.parse().unwrap()).collect()
+    }
+
+    /// Card `id` with `matches` winning numbers
+    fn card(id: u32, matches: u8) -> Scratchcard {
+        let numbers = (1..=matches)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("Card {id}: 0 {numbers} | {numbers} 255")
+            .parse()
+            .unwrap()
+    }
+
+    fn rule(policy: OutOfRange) -> CopyCascade {
+        CopyCascade {
+            policy,
+            ..CopyCascade::default()
+        }
+    }
+
+    fn amounts(held: Result<Vec<Held>, CascadeError>) -> Vec<(u32, usize)> {
+        held.unwrap()
+            .iter()
+            .map(|held| (held.card.id, held.amount))
+            .collect()
+    }
+
+    #[test]
+    fn unordered_cards() {
+        let cards = [card(3, 0), card(1, 2), card(2, 1)];
+
+        assert_eq!(
+            amounts(cascade(&cards, CopyCascade::default())),
+            [(1, 1), (2, 2), (3, 4)]
+        );
+    }
+
+    #[test]
+    fn duplicate_cards() {
+        let cards = [card(1, 1), card(2, 0), card(1, 0)];
+
+        assert_eq!(
+            cascade(&cards, CopyCascade::default()).unwrap_err(),
+            CascadeError::DuplicateCard(1)
+        );
+    }
+
+    #[test]
+    fn sparse_ids_follow_the_policy() {
+        // Card 1 wins copies of 2, which is missing, and 3
+        let cards = [card(1, 2), card(3, 1), card(4, 0)];
+
+        assert_eq!(
+            amounts(cascade(&cards, rule(OutOfRange::Ignore))),
+            [(1, 1), (3, 2), (4, 3)]
+        );
+        assert_eq!(
+            amounts(cascade(&cards, rule(OutOfRange::Clamp))),
+            [(1, 1), (3, 3), (4, 4)]
+        );
+        assert_eq!(
+            cascade(&cards, rule(OutOfRange::Error)).unwrap_err(),
+            CascadeError::OutOfRange {
+                card: 1,
+                copy: Some(2)
+            }
+        );
+    }
+
+    #[test]
+    fn copies_past_the_last_card_follow_the_policy() {
+        // Card 2 wins copies of 3 and 4, card 3 is the last card and wins a copy of 4
+        let cards = [card(1, 1), card(2, 2), card(3, 1)];
+
+        assert_eq!(
+            amounts(cascade(&cards, rule(OutOfRange::Ignore))),
+            [(1, 1), (2, 2), (3, 3)]
+        );
+
+        let held = cascade(&cards, rule(OutOfRange::Clamp)).unwrap();
+        assert_eq!(amounts(Ok(held.clone())), [(1, 1), (2, 2), (3, 5)]);
+        // Both of card 2's copies go to card 3, merged into one entry
+        assert_eq!(held[2].won_from, [(2, 4)]);
+
+        assert_eq!(
+            cascade(&cards, rule(OutOfRange::Error)).unwrap_err(),
+            CascadeError::OutOfRange {
+                card: 2,
+                copy: Some(4)
+            }
+        );
+    }
+
+    #[test]
+    fn copy_ids_past_u32_max_follow_the_policy() {
+        let cards = cards(&format!(
+            "Card {}: 1 2 | 1 2\nCard {}: 1 | 2",
+            u32::MAX - 1,
+            u32::MAX
+        ));
+        let rule = |stride, policy| CopyCascade {
+            stride,
+            cap: None,
+            policy,
+        };
+
+        let amounts = |held: Vec<Held>| held.iter().map(|held| held.amount).collect::<Vec<_>>();
+
+        assert_eq!(
+            amounts(cascade(&cards, rule(1, OutOfRange::Ignore)).unwrap()),
+            [1, 2]
+        );
+        assert_eq!(
+            amounts(cascade(&cards, rule(3_000_000_000, OutOfRange::Clamp)).unwrap()),
+            [1, 3]
+        );
+        assert_eq!(
+            cascade(&cards, rule(1, OutOfRange::Error)).unwrap_err(),
+            CascadeError::OutOfRange {
+                card: u32::MAX - 1,
+                copy: None
+            }
+        );
+    }
+}
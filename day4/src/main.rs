@@ -1,81 +1,90 @@
-use std::{
-    collections::{HashMap, HashSet},
-    str::FromStr,
-};
+pub mod cascade;
+pub mod report;
+pub mod scoring;
+pub mod scratchcard;
 
-static INPUT: &str = include_str!("input.txt");
-
-fn main() {
-    let part1 = part1(INPUT);
-    let part2 = part2(INPUT);
+use std::{env, process, str::FromStr};
 
-    println!("{part1}");
-    println!("{part2}");
-}
+use cascade::{cascade, CopyCascade};
+use report::Report;
+use scoring::{parse_rule, DoublingPoints, ScoreError, Scoring};
+use scratchcard::Scratchcard;
 
-pub fn part1(input: &str) -> usize {
-    let mut point_sum = 0;
-
-    for line in input.lines() {
-        let (_, numbers) = line.split_once(':').unwrap();
+static INPUT: &str = include_str!("input.txt");
 
-        let (winning_numbers, numbers) = numbers.split_once('|').unwrap();
+fn main() {
+    let cards = parse(INPUT).unwrap();
 
-        let winning_numbers: HashSet<u8> = winning_numbers
-            .split_ascii_whitespace()
-            .map(FromStr::from_str)
-            .collect::<Result<_, _>>()
-            .unwrap();
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
 
-        let winning_numbers_gotten = numbers
-            .split_ascii_whitespace()
-            .map(|n| n.parse().unwrap())
-            .filter(|n| winning_numbers.contains(n))
-            .count();
+    match args.as_slice() {
+        ["trace", format @ ..] => {
+            let held = cascade(&cards, CopyCascade::default()).unwrap();
+            let report = Report::new(&held);
 
-        let points = match winning_numbers_gotten {
-            0 => 0,
-            n => 2_usize.pow(n as u32 - 1),
-        };
+            match format {
+                [] | ["table"] => print!("{}", report.to_table()),
+                ["json"] => println!("{}", report.to_json().unwrap()),
+                _ => panic!("Usage: trace [table|json]"),
+            }
 
-        point_sum += points;
+            return;
+        }
+        ["score", rule @ ..] => {
+            let rule = parse_rule(rule).expect(
+                "Usage: score doubling | linear [per_match] | cascade [stride=N] [cap=N] [clamp|ignore|error]",
+            );
+
+            match rule.score(&cards) {
+                Ok(score) => println!("{score}"),
+                Err(error) => {
+                    eprintln!("{error}");
+                    process::exit(1);
+                }
+            }
+
+            return;
+        }
+        _ => {}
     }
 
-    point_sum
-}
-
-pub fn part2(input: &str) -> usize {
-    let mut all_cards = HashMap::with_capacity(220);
-
-    let mut winning_numbers_set = HashSet::with_capacity(10);
+    let part1 = part1(&cards).unwrap();
+    let part2 = part2(&cards, CopyCascade::default()).unwrap();
 
-    for (i, line) in input.lines().enumerate() {
-        let scratchcard_id = i + 1;
+    println!("{part1}");
+    println!("{part2}");
+}
 
-        let &mut amount = all_cards.entry(scratchcard_id).or_insert(1);
+pub fn parse(input: &str) -> Result<Vec<Scratchcard>, Box<dyn std::error::Error>> {
+    input.lines().map(Scratchcard::from_str).collect()
+}
 
-        let (_, numbers) = line.split_once(':').unwrap();
+pub fn part1(cards: &[Scratchcard]) -> Result<usize, ScoreError> {
+    DoublingPoints.score(cards)
+}
 
-        let (winning_numbers, numbers) = numbers.split_once('|').unwrap();
+pub fn part2(cards: &[Scratchcard], rule: CopyCascade) -> Result<usize, ScoreError> {
+    rule.score(cards)
+}
 
-        winning_numbers_set.extend(
-            winning_numbers
-                .split_ascii_whitespace()
-                .map(|n| n.parse::<u8>().unwrap()),
-        );
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
 
-        let winning_numbers_gotten = numbers
-            .split_ascii_whitespace()
-            .map(|n| n.parse().unwrap())
-            .filter(|n| winning_numbers_set.contains(n))
-            .count();
+    pub(crate) const SAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
-        winning_numbers_set.clear();
+    #[test]
+    fn sample() {
+        let cards = parse(SAMPLE).unwrap();
 
-        for scratchcard in scratchcard_id + 1..=scratchcard_id + winning_numbers_gotten {
-            *all_cards.entry(scratchcard).or_insert(1) += amount;
-        }
+        assert_eq!(part1(&cards), Ok(13));
+        assert_eq!(part2(&cards, CopyCascade::default()), Ok(30));
     }
-
-    all_cards.into_values().sum()
 }
@@ -0,0 +1,163 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::cascade::Held;
+
+/// How every card's copies were won in part 2
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub cards: Vec<CardReport>,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CardReport {
+    pub id: u32,
+    pub matches: u32,
+    pub copies: usize,
+    pub won_from: Vec<WonFrom>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WonFrom {
+    pub card: u32,
+    pub copies: usize,
+}
+
+impl Report {
+    pub fn new(held: &[Held]) -> Self {
+        let cards = held
+            .iter()
+            .map(|held| CardReport {
+                id: held.card.id,
+                matches: held.card.matches(),
+                copies: held.amount,
+                won_from: held
+                    .won_from
+                    .iter()
+                    .map(|&(card, copies)| WonFrom { card, copies })
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            cards,
+            total: held.iter().map(|held| held.amount).sum(),
+        }
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "{:>6} {:>8} {:>10}  Won from\n",
+            "Card", "Matches", "Copies"
+        );
+
+        for card in &self.cards {
+            let won_from = card
+                .won_from
+                .iter()
+                .map(|won| format!("{}x{}", won.card, won.copies))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let row = format!(
+                "{:>6} {:>8} {:>10}  {won_from}",
+                card.id, card.matches, card.copies
+            );
+            writeln!(table, "{}", row.trim_end()).unwrap();
+        }
+
+        writeln!(table, "{:>6} {:>8} {:>10}", "Total", "", self.total).unwrap();
+
+        table
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cascade::{cascade, CopyCascade},
+        parse,
+        tests::SAMPLE,
+    };
+
+    fn sample() -> Report {
+        let cards = parse(SAMPLE).unwrap();
+        Report::new(&cascade(&cards, CopyCascade::default()).unwrap())
+    }
+
+    #[test]
+    fn won_from() {
+        let report = sample();
+
+        let won_from = report
+            .cards
+            .iter()
+            .map(|card| {
+                card.won_from
+                    .iter()
+                    .map(|won| (won.card, won.copies))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            won_from,
+            [
+                vec![],
+                vec![(1, 1)],
+                vec![(1, 1), (2, 2)],
+                vec![(1, 1), (2, 2), (3, 4)],
+                vec![(1, 1), (3, 4), (4, 8)],
+                vec![],
+            ]
+        );
+        assert_eq!(
+            report
+                .cards
+                .iter()
+                .map(|card| card.copies)
+                .collect::<Vec<_>>(),
+            [1, 2, 4, 8, 14, 1]
+        );
+        assert_eq!(report.total, 30);
+    }
+
+    #[test]
+    fn table() {
+        assert_eq!(
+            sample().to_table(),
+            "  Card  Matches     Copies  Won from
+     1        4          1
+     2        2          2  1x1
+     3        2          4  1x1 2x2
+     4        1          8  1x1 2x2 3x4
+     5        0         14  1x1 3x4 4x8
+     6        0          1
+ Total                  30
+"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let json: serde_json::Value = serde_json::from_str(&sample().to_json().unwrap()).unwrap();
+
+        assert_eq!(json["total"], 30);
+        assert_eq!(json["cards"][4]["copies"], 14);
+        assert_eq!(
+            json["cards"][3]["won_from"],
+            serde_json::json!([
+                { "card": 1, "copies": 1 },
+                { "card": 2, "copies": 2 },
+                { "card": 3, "copies": 4 },
+            ])
+        );
+    }
+}
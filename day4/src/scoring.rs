@@ -0,0 +1,187 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    cascade::{cascade, CascadeError, CopyCascade, OutOfRange},
+    scratchcard::Scratchcard,
+};
+
+pub trait Scoring {
+    fn score(&self, cards: &[Scratchcard]) -> Result<usize, ScoreError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoreError {
+    Cascade(CascadeError),
+    /// The points of the card, or the total once they are added, don't fit in a usize
+    TooManyPoints(u32),
+}
+
+impl Display for ScoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScoreError::Cascade(error) => write!(f, "{error}"),
+            ScoreError::TooManyPoints(id) => {
+                write!(f, "Points up to card {id} don't fit in a usize")
+            }
+        }
+    }
+}
+
+impl Error for ScoreError {}
+
+impl From<CascadeError> for ScoreError {
+    fn from(error: CascadeError) -> Self {
+        ScoreError::Cascade(error)
+    }
+}
+
+/// Adds up the points of every card, failing on the first one which doesn't fit
+fn total(
+    cards: &[Scratchcard],
+    points: impl Fn(&Scratchcard) -> Option<usize>,
+) -> Result<usize, ScoreError> {
+    cards.iter().try_fold(0usize, |total, card| {
+        points(card)
+            .and_then(|points| total.checked_add(points))
+            .ok_or(ScoreError::TooManyPoints(card.id))
+    })
+}
+
+/// Part 1 rule, the first match is worth a point and every other one doubles it
+#[derive(Debug, Clone, Copy)]
+pub struct DoublingPoints;
+
+impl Scoring for DoublingPoints {
+    fn score(&self, cards: &[Scratchcard]) -> Result<usize, ScoreError> {
+        // Cards can have up to 256 matches, far more doublings than a usize holds
+        total(cards, |card| match card.matches() {
+            0 => Some(0),
+            n => 2_usize.checked_pow(n - 1),
+        })
+    }
+}
+
+/// Every match is worth `per_match` points
+#[derive(Debug, Clone, Copy)]
+pub struct LinearPoints {
+    pub per_match: usize,
+}
+
+impl Scoring for LinearPoints {
+    fn score(&self, cards: &[Scratchcard]) -> Result<usize, ScoreError> {
+        total(cards, |card| {
+            (card.matches() as usize).checked_mul(self.per_match)
+        })
+    }
+}
+
+impl Scoring for CopyCascade {
+    fn score(&self, cards: &[Scratchcard]) -> Result<usize, ScoreError> {
+        cascade(cards, *self)?
+            .into_iter()
+            .try_fold(0usize, |total, held| {
+                total
+                    .checked_add(held.amount)
+                    .ok_or(ScoreError::TooManyPoints(held.card.id))
+            })
+    }
+}
+
+/// Picks a rule from the runner's arguments, e.g. `linear 2` or `cascade stride=2 cap=3 clamp`
+pub fn parse_rule(args: &[&str]) -> Option<Box<dyn Scoring>> {
+    Some(match args {
+        ["doubling"] => Box::new(DoublingPoints),
+        ["linear"] => Box::new(LinearPoints { per_match: 1 }),
+        ["linear", per_match] => Box::new(LinearPoints {
+            per_match: per_match.parse().ok()?,
+        }),
+        ["cascade", options @ ..] => {
+            let mut rule = CopyCascade::default();
+
+            for &option in options {
+                match option.split_once('=') {
+                    // A stride of 0 would only ever win copies of the card itself
+                    Some(("stride", stride)) => {
+                        rule.stride = stride.parse().ok().filter(|&stride| stride > 0)?
+                    }
+                    Some(("cap", cap)) => rule.cap = Some(cap.parse().ok()?),
+                    None if option == "clamp" => rule.policy = OutOfRange::Clamp,
+                    None if option == "ignore" => rule.policy = OutOfRange::Ignore,
+                    None if option == "error" => rule.policy = OutOfRange::Error,
+                    _ => return None,
+                }
+            }
+
+            Box::new(rule)
+        }
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, tests::SAMPLE};
+
+    fn score(rule: &[&str], input: &str) -> Result<usize, ScoreError> {
+        parse_rule(rule).unwrap().score(&parse(input).unwrap())
+    }
+
+    #[test]
+    fn rules_on_sample() {
+        // The sample cards have 4, 2, 2, 1, 0 and 0 matches
+        assert_eq!(score(&["doubling"], SAMPLE), Ok(13));
+        assert_eq!(score(&["linear"], SAMPLE), Ok(9));
+        assert_eq!(score(&["linear", "3"], SAMPLE), Ok(27));
+        assert_eq!(score(&["cascade"], SAMPLE), Ok(30));
+        // Card 1 wins 3 and 5, card 2 wins 4 and 6, card 3 wins 5 and 7, card 4 wins 6
+        assert_eq!(
+            score(&["cascade", "stride=2"], SAMPLE),
+            Ok(1 + 1 + 2 + 2 + 4 + 4)
+        );
+        // Every card wins at most one copy, of the next card
+        assert_eq!(
+            score(&["cascade", "cap=1"], SAMPLE),
+            Ok(1 + 2 + 3 + 4 + 5 + 1)
+        );
+        assert_eq!(
+            score(&["cascade", "stride=2", "cap=1", "error"], SAMPLE),
+            Ok(1 + 1 + 2 + 2 + 3 + 3)
+        );
+        assert_eq!(
+            score(&["cascade", "stride=2", "error"], SAMPLE),
+            Err(ScoreError::Cascade(CascadeError::OutOfRange {
+                card: 1,
+                copy: Some(7)
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_rule_rejects_bad_options() {
+        for rule in [
+            &[][..],
+            &["points"],
+            &["linear", "x"],
+            &["linear", "1", "2"],
+            &["cascade", "stride=0"],
+            &["cascade", "stride=x"],
+            &["cascade", "cap=-1"],
+            &["cascade", "wrap"],
+        ] {
+            assert!(parse_rule(rule).is_none(), "{rule:?}");
+        }
+    }
+
+    #[test]
+    fn doubling_overflow() {
+        let numbers = (0..65).map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+        let input = format!("Card 7: {numbers} | {numbers}");
+
+        assert_eq!(
+            score(&["doubling"], &input),
+            Err(ScoreError::TooManyPoints(7))
+        );
+        assert_eq!(score(&["linear"], &input), Ok(65));
+    }
+}
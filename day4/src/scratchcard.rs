@@ -0,0 +1,130 @@
+use std::{error::Error, ops::BitAnd, str::FromStr};
+
+#[derive(Debug, Clone)]
+pub struct Scratchcard {
+    pub id: u32,
+    pub winning: NumberSet,
+    pub have: NumberSet,
+}
+
+impl Scratchcard {
+    pub fn matches(&self) -> u32 {
+        (self.winning & self.have).len()
+    }
+}
+
+impl FromStr for Scratchcard {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (card, numbers) = s.split_once(':').ok_or("Missing ':' after card id")?;
+        let (winning, have) = numbers
+            .split_once('|')
+            .ok_or("Missing '|' between number lists")?;
+
+        let id = card
+            .strip_prefix("Card")
+            .ok_or("Missing 'Card' prefix")?
+            .trim()
+            .parse()?;
+
+        Ok(Self {
+            id,
+            winning: winning.parse()?,
+            have: have.parse()?,
+        })
+    }
+}
+
+/// Set of numbers on a card, one bit for every possible `u8`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NumberSet([u64; 4]);
+
+impl NumberSet {
+    pub fn insert(&mut self, n: u8) {
+        self.0[usize::from(n / 64)] |= 1 << (n % 64);
+    }
+
+    pub fn contains(&self, n: u8) -> bool {
+        self.0[usize::from(n / 64)] & (1 << (n % 64)) != 0
+    }
+
+    pub fn len(&self) -> u32 {
+        self.0.iter().map(|bits| bits.count_ones()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == [0; 4]
+    }
+}
+
+impl BitAnd for NumberSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self([0, 1, 2, 3].map(|i| self.0[i] & rhs.0[i]))
+    }
+}
+
+impl FromIterator<u8> for NumberSet {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut set = Self::default();
+
+        for n in iter {
+            set.insert(n);
+        }
+
+        set
+    }
+}
+
+impl FromStr for NumberSet {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_ascii_whitespace()
+            .map(|n| n.parse::<u8>().map_err(Into::into))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_errors() {
+        let error = |s: &str| s.parse::<Scratchcard>().unwrap_err().to_string();
+
+        assert_eq!(error("1: 1 2 | 3"), "Missing 'Card' prefix");
+        assert_eq!(error("Card 1 1 2 | 3"), "Missing ':' after card id");
+        assert_eq!(error("Card 1: 1 2 3"), "Missing '|' between number lists");
+        assert!("Card x: 1 | 2".parse::<Scratchcard>().is_err());
+        assert!("Card 1: 256 | 2".parse::<Scratchcard>().is_err());
+
+        let card = "Card  12: 1 2 | 2 3".parse::<Scratchcard>().unwrap();
+        assert_eq!((card.id, card.matches()), (12, 1));
+    }
+
+    #[test]
+    fn number_set_word_boundaries() {
+        let boundaries = [0, 63, 64, 127, 128, 191, 192, 255];
+        let set = boundaries.into_iter().collect::<NumberSet>();
+
+        assert_eq!(set.len(), 8);
+        assert!(boundaries.iter().all(|&n| set.contains(n)));
+        assert!([1, 62, 65, 126, 129, 254].iter().all(|&n| !set.contains(n)));
+
+        let other = [63, 128, 255, 5].into_iter().collect::<NumberSet>();
+        let both = set & other;
+
+        assert_eq!(both.len(), 3);
+        assert!(both.contains(63) && both.contains(128) && both.contains(255));
+        assert!(!both.contains(64) && !both.contains(5));
+
+        let mut empty = NumberSet::default();
+        assert!(empty.is_empty() && (set & empty).is_empty());
+        empty.insert(255);
+        assert_eq!(empty.len(), 1);
+    }
+}
@@ -0,0 +1,47 @@
+use day5::{parsing::parse_data, Almanac, Mapper};
+use divan::{black_box, Bencher};
+
+static INPUT: &str = include_str!("../src/input.txt");
+
+fn main() {
+    divan::main();
+}
+
+/// The first few thousand seeds of every seed range
+fn seeds(almanac: &Almanac) -> Vec<u64> {
+    almanac
+        .seed_ranges()
+        .unwrap()
+        .into_iter()
+        .flat_map(|seeds| seeds.take(1000))
+        .collect()
+}
+
+fn lowest_location(almanac: &Almanac, seeds: &[u64], map: fn(&Mapper, u64) -> u64) -> u64 {
+    seeds
+        .iter()
+        .map(|&seed| {
+            almanac
+                .mappers
+                .iter()
+                .fold(seed, |value, mapper| map(mapper, value))
+        })
+        .min()
+        .unwrap()
+}
+
+#[divan::bench]
+fn linear_scan(bencher: Bencher) {
+    let almanac = parse_data(INPUT).unwrap();
+    let seeds = seeds(&almanac);
+
+    bencher.bench_local(|| lowest_location(&almanac, black_box(&seeds), Mapper::map_linear));
+}
+
+#[divan::bench]
+fn binary_search(bencher: Bencher) {
+    let almanac = parse_data(INPUT).unwrap();
+    let seeds = seeds(&almanac);
+
+    bencher.bench_local(|| lowest_location(&almanac, black_box(&seeds), Mapper::map));
+}
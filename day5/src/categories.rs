@@ -0,0 +1,234 @@
+use std::{collections::HashSet, error::Error, fmt::Display};
+
+use crate::{piecewise::Piecewise, Almanac, Mapper, LOCATION, SEED};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CategoryError {
+    DuplicateMapper {
+        source: String,
+        destination: String,
+    },
+    Cycle(String),
+    NoPath {
+        from: String,
+        to: String,
+    },
+    /// Two chains of maps go from `from` to `to` but map some value differently
+    ConflictingPaths {
+        from: String,
+        to: String,
+    },
+}
+
+impl Display for CategoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CategoryError::DuplicateMapper {
+                source,
+                destination,
+            } => write!(f, "There is more than one {source}-to-{destination} map"),
+            CategoryError::Cycle(category) => {
+                write!(f, "The {category} category can be mapped back to itself")
+            }
+            CategoryError::NoPath { from, to } => {
+                write!(f, "No chain of maps goes from {from} to {to}")
+            }
+            CategoryError::ConflictingPaths { from, to } => write!(
+                f,
+                "Different chains of maps from {from} to {to} give different values"
+            ),
+        }
+    }
+}
+
+impl Error for CategoryError {}
+
+impl Almanac {
+    /// Checks the maps form a chain, or at least a DAG, of categories going from seed to location
+    ///
+    /// Categories can be joined by more than one chain of maps as long as they all agree, so it
+    /// doesn't matter which one [`Almanac::path`] picks. The solvers and [`Almanac::compose`] expect
+    /// this to pass.
+    pub fn validate_categories(&self) -> Result<(), CategoryError> {
+        let mut seen = HashSet::new();
+
+        for mapper in &self.mappers {
+            if !seen.insert((&mapper.source, &mapper.destination)) {
+                return Err(CategoryError::DuplicateMapper {
+                    source: mapper.source.clone(),
+                    destination: mapper.destination.clone(),
+                });
+            }
+        }
+
+        for mapper in &self.mappers {
+            if self.path(&mapper.destination, &mapper.source).is_ok() {
+                return Err(CategoryError::Cycle(mapper.source.clone()));
+            }
+        }
+
+        for from in self.mappers.iter().map(|mapper| &mapper.source) {
+            let mut routes = Vec::new();
+            self.routes(from, &mut Vec::new(), &mut routes);
+
+            for (i, route) in routes.iter().enumerate() {
+                let to = &route.last().unwrap().destination;
+                let composed = compose(route);
+
+                if routes[..i].iter().any(|other| {
+                    &other.last().unwrap().destination == to && compose(other) != composed
+                }) {
+                    return Err(CategoryError::ConflictingPaths {
+                        from: from.clone(),
+                        to: to.clone(),
+                    });
+                }
+            }
+        }
+
+        self.path(SEED, LOCATION)?;
+
+        Ok(())
+    }
+
+    /// Every chain of maps starting at `from`, the maps can't have cycles
+    fn routes<'a>(
+        &'a self,
+        from: &str,
+        route: &mut Vec<&'a Mapper>,
+        routes: &mut Vec<Vec<&'a Mapper>>,
+    ) {
+        for mapper in self.mappers.iter().filter(|mapper| mapper.source == from) {
+            route.push(mapper);
+            routes.push(route.clone());
+            self.routes(&mapper.destination, route, routes);
+            route.pop();
+        }
+    }
+
+    /// Maps to go through, in order, to get from the `from` category to the `to` one
+    ///
+    /// If there is more than one way the first one found, in the order of the maps, is used.
+    /// [`Almanac::validate_categories`] checks they all give the same values.
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&Mapper>, CategoryError> {
+        let mut path = Vec::new();
+        let mut visited = HashSet::new();
+
+        if from == to || self.find_path(from, to, &mut path, &mut visited) {
+            Ok(path)
+        } else {
+            Err(CategoryError::NoPath {
+                from: from.to_owned(),
+                to: to.to_owned(),
+            })
+        }
+    }
+
+    fn find_path<'a>(
+        &'a self,
+        from: &str,
+        to: &str,
+        path: &mut Vec<&'a Mapper>,
+        visited: &mut HashSet<&'a str>,
+    ) -> bool {
+        for mapper in self.mappers.iter().filter(|mapper| mapper.source == from) {
+            if !visited.insert(&mapper.destination) {
+                continue;
+            }
+
+            path.push(mapper);
+
+            if mapper.destination == to || self.find_path(&mapper.destination, to, path, visited) {
+                return true;
+            }
+
+            path.pop();
+        }
+
+        false
+    }
+
+    pub fn map(&self, from: &str, to: &str, value: u64) -> Result<u64, CategoryError> {
+        Ok(self
+            .path(from, to)?
+            .into_iter()
+            .fold(value, |value, mapper| mapper.map(value)))
+    }
+}
+
+fn compose(route: &[&Mapper]) -> Piecewise {
+    route
+        .iter()
+        .fold(Piecewise::identity(), |composed, mapper| {
+            composed.then(&mapper.table)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parsing::parse_data, solving::tests::SAMPLE};
+
+    fn categories(input: &str) -> Result<(), CategoryError> {
+        parse_data(&format!("seeds: 1 2{input}"))
+            .unwrap()
+            .validate_categories()
+    }
+
+    #[test]
+    fn map_between_categories() {
+        let almanac = parse_data(SAMPLE).unwrap();
+
+        assert_eq!(almanac.validate_categories(), Ok(()));
+        assert_eq!(almanac.map("seed", "soil", 79), Ok(81));
+        assert_eq!(almanac.map("soil", "light", 81), Ok(74));
+        assert_eq!(almanac.map("water", "location", 81), Ok(82));
+        assert_eq!(almanac.map("seed", "location", 79), Ok(82));
+        assert_eq!(almanac.map("light", "light", 3), Ok(3));
+        assert_eq!(
+            almanac.map("location", "seed", 82),
+            Err(CategoryError::NoPath {
+                from: "location".to_owned(),
+                to: "seed".to_owned()
+            })
+        );
+        assert!(almanac.map("seed", "colour", 1).is_err());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            categories("\n\nseed-to-location map:\n1 2 3\n\nseed-to-location map:\n4 5 6"),
+            Err(CategoryError::DuplicateMapper {
+                source: "seed".to_owned(),
+                destination: "location".to_owned()
+            })
+        );
+        assert_eq!(
+            categories("\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-seed map:\n4 5 6"),
+            Err(CategoryError::Cycle("seed".to_owned()))
+        );
+        assert_eq!(
+            categories("\n\nseed-to-soil map:\n1 2 3"),
+            Err(CategoryError::NoPath {
+                from: "seed".to_owned(),
+                to: "location".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn several_paths_have_to_agree() {
+        let agreeing = "\n\nseed-to-soil map:\n10 0 5\n\nsoil-to-location map:\n20 10 5\n\nseed-to-location map:\n20 0 5\n20 10 5";
+        assert_eq!(categories(agreeing), Ok(()));
+
+        let conflicting = agreeing.replace("20 0 5", "30 0 5");
+        assert_eq!(
+            categories(&conflicting),
+            Err(CategoryError::ConflictingPaths {
+                from: "seed".to_owned(),
+                to: "location".to_owned()
+            })
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use std::{fmt::Write, ops::Range};
+
+use crate::{categories::CategoryError, Almanac, LOCATION, SEED};
+
+const COLUMN_SPACING: f64 = 200.0;
+const MARGIN: f64 = 40.0;
+const HEIGHT: f64 = 600.0;
+
+/// Part of a seed interval which every layer shifts in one piece
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Band {
+    /// Index of the seed interval the band was split from
+    pub seeds: usize,
+    /// Values of the band in every category, starting with the seeds
+    pub spans: Vec<Range<u64>>,
+}
+
+/// Seed intervals followed from seed to location, for drawing how the layers split and move them
+#[derive(Debug, Clone)]
+pub struct Flow {
+    pub categories: Vec<String>,
+    pub bands: Vec<Band>,
+}
+
+impl Almanac {
+    pub fn flow(&self, seeds: &[Range<u64>]) -> Result<Flow, CategoryError> {
+        let mappers = self.path(SEED, LOCATION)?;
+
+        let mut categories = vec![SEED.to_owned()];
+        categories.extend(mappers.iter().map(|mapper| mapper.destination.clone()));
+
+        let mut bands = seeds
+            .iter()
+            .enumerate()
+            .filter(|(_, span)| !span.is_empty())
+            .map(|(i, span)| Band {
+                seeds: i,
+                spans: vec![span.clone()],
+            })
+            .collect::<Vec<_>>();
+
+        for mapper in mappers {
+            bands = bands
+                .into_iter()
+                .flat_map(|band| {
+                    let last = band.spans.last().unwrap().clone();
+                    let mut offset = 0;
+
+                    // `map_range` keeps the order of the values, so each piece starts where the last one ended
+                    mapper
+                        .table
+                        .map_range(last.start..=last.end - 1)
+                        .into_iter()
+                        .map(|mapped| {
+                            let len = mapped.end() - mapped.start() + 1;
+                            let mut spans = band
+                                .spans
+                                .iter()
+                                .map(|span| span.start + offset..span.start + offset + len)
+                                .collect::<Vec<_>>();
+                            spans.push(*mapped.start()..*mapped.end() + 1);
+                            offset += len;
+
+                            Band {
+                                seeds: band.seeds,
+                                spans,
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+        }
+
+        Ok(Flow { categories, bands })
+    }
+}
+
+impl Flow {
+    /// Sankey-style diagram with a column per category and values growing downwards
+    pub fn to_svg(&self) -> String {
+        let width = 2.0 * MARGIN + COLUMN_SPACING * (self.categories.len() - 1) as f64;
+        let height = 2.0 * MARGIN + HEIGHT;
+
+        let max = self
+            .bands
+            .iter()
+            .flat_map(|band| &band.spans)
+            .map(|span| span.end)
+            .max()
+            .unwrap_or(1);
+        let x = |column: usize| MARGIN + COLUMN_SPACING * column as f64;
+        let y = |value: u64| MARGIN + HEIGHT * value as f64 / max as f64;
+
+        let seed_intervals = self
+            .bands
+            .iter()
+            .map(|band| band.seeds)
+            .max()
+            .map_or(0, |i| i + 1);
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        )
+        .unwrap();
+        writeln!(svg, r#"  <rect width="100%" height="100%" fill="white"/>"#).unwrap();
+
+        for band in &self.bands {
+            let hue = 360 * band.seeds / seed_intervals;
+
+            for (column, pair) in band.spans.windows(2).enumerate() {
+                let (from, to) = (&pair[0], &pair[1]);
+                let (x0, x1) = (x(column), x(column + 1));
+                let middle = (x0 + x1) / 2.0;
+
+                writeln!(
+                    svg,
+                    r#"  <path d="M {x0} {} C {middle} {} {middle} {} {x1} {} L {x1} {} C {middle} {} {middle} {} {x0} {} Z" fill="hsl({hue}, 70%, 50%)" fill-opacity="0.6"><title>{}..{} -> {}..{}</title></path>"#,
+                    y(from.start),
+                    y(from.start),
+                    y(to.start),
+                    y(to.start),
+                    y(to.end),
+                    y(to.end),
+                    y(from.end),
+                    y(from.end),
+                    from.start,
+                    from.end,
+                    to.start,
+                    to.end
+                )
+                .unwrap();
+            }
+        }
+
+        for (column, category) in self.categories.iter().enumerate() {
+            let x = x(column);
+
+            writeln!(
+                svg,
+                r#"  <line x1="{x}" y1="{MARGIN}" x2="{x}" y2="{}" stroke="black"/>"#,
+                MARGIN + HEIGHT
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                r#"  <text x="{x}" y="{}" text-anchor="middle" font-family="sans-serif" font-size="14">{category}</text>"#,
+                MARGIN / 2.0
+            )
+            .unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parsing::parse_data, solving::tests::SAMPLE};
+
+    /// Sorted with touching ranges joined, so differently split ranges compare equal
+    fn merged(mut ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
+        ranges.sort_unstable_by_key(|range| range.start);
+        let mut merged: Vec<Range<u64>> = Vec::new();
+
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if last.end >= range.start => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        merged
+    }
+
+    #[test]
+    fn bands_follow_the_layers() {
+        for input in [SAMPLE, include_str!("input.txt")] {
+            let almanac = parse_data(input).unwrap();
+            let seeds = almanac.seed_ranges().unwrap();
+            let composed = almanac.compose();
+            let flow = almanac.flow(&seeds).unwrap();
+
+            assert_eq!(flow.categories.len(), 8);
+            assert_eq!(flow.categories.first().unwrap(), SEED);
+            assert_eq!(flow.categories.last().unwrap(), LOCATION);
+
+            for band in &flow.bands {
+                assert_eq!(band.spans.len(), flow.categories.len());
+
+                let len = band.spans[0].end - band.spans[0].start;
+                assert!(len > 0);
+                assert!(band.spans.iter().all(|span| span.end - span.start == len));
+                assert_eq!(
+                    composed.map(band.spans[0].start),
+                    band.spans.last().unwrap().start
+                );
+            }
+
+            for (i, seeds) in seeds.iter().enumerate() {
+                let bands = flow.bands.iter().filter(|band| band.seeds == i);
+
+                let total = bands
+                    .clone()
+                    .map(|band| band.spans[0].end - band.spans[0].start);
+                assert_eq!(total.sum::<u64>(), seeds.end - seeds.start);
+
+                let locations = bands
+                    .map(|band| band.spans.last().unwrap().clone())
+                    .collect();
+                let expected = composed
+                    .map_range(seeds.start..=seeds.end - 1)
+                    .into_iter()
+                    .map(|range| *range.start()..*range.end() + 1)
+                    .collect();
+                assert_eq!(merged(locations), merged(expected));
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+pub mod categories;
+pub mod flow;
+pub mod parsing;
+pub mod piecewise;
+pub mod solving;
+pub mod trace;
+pub mod validation;
+pub mod writer;
+
+use std::{error::Error, fmt::Display, ops::Range};
+
+use piecewise::Piecewise;
+use serde::Serialize;
+
+pub const SEED: &str = "seed";
+pub const LOCATION: &str = "location";
+
+#[derive(Debug)]
+pub struct Almanac {
+    /// Seed numbers as written, see [`Almanac::individual_seeds`] and [`Almanac::seed_ranges`]
+    pub seeds: Vec<u64>,
+    pub mappers: Vec<Mapper>,
+}
+
+impl Almanac {
+    /// Part 1 reading of the seeds, every number is a seed
+    pub fn individual_seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    /// Part 2 reading of the seeds, pairs of range start and length
+    pub fn seed_ranges(&self) -> Result<Vec<Range<u64>>, SeedRangeError> {
+        let pairs = self.seeds.chunks_exact(2);
+
+        if !pairs.remainder().is_empty() {
+            return Err(SeedRangeError::OddCount(self.seeds.len()));
+        }
+
+        pairs
+            .map(|pair| {
+                let (start, len) = (pair[0], pair[1]);
+                let end = start
+                    .checked_add(len)
+                    .ok_or(SeedRangeError::Overflow { start, len })?;
+
+                Ok(start..end)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeedRangeError {
+    OddCount(usize),
+    Overflow {
+        start: u64,
+        len: u64,
+    },
+    /// Every range has a length of 0, so there is no lowest location
+    Empty,
+}
+
+impl Display for SeedRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeedRangeError::OddCount(count) => write!(
+                f,
+                "Seed ranges need pairs of start and length but there are {count} seed numbers"
+            ),
+            SeedRangeError::Overflow { start, len } => {
+                write!(f, "Seed range {start} {len} goes past u64::MAX")
+            }
+            SeedRangeError::Empty => write!(f, "Every seed range is empty"),
+        }
+    }
+}
+
+impl Error for SeedRangeError {}
+
+#[derive(Debug)]
+pub struct Mapper {
+    pub source: String,
+    pub destination: String,
+    pub ranges: Vec<MapRange>,
+    /// `ranges` sorted, without overlaps and with the values between them mapping to themselves
+    pub table: Piecewise,
+}
+
+impl Mapper {
+    pub fn new(source: String, destination: String, ranges: Vec<MapRange>) -> Self {
+        Self {
+            table: Piecewise::from_ranges(&ranges),
+            source,
+            destination,
+            ranges,
+        }
+    }
+
+    /// Binary searches the normalised table
+    pub fn map(&self, value: u64) -> u64 {
+        self.table.map(value)
+    }
+
+    /// Scans the ranges in the order they were given, kept to compare against [`Mapper::map`]
+    pub fn map_linear(&self, value: u64) -> u64 {
+        self.ranges
+            .iter()
+            .find_map(|range| range.map(value))
+            .unwrap_or(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MapRange {
+    pub destination: u64,
+    pub source: u64,
+    pub length: u64,
+}
+
+impl MapRange {
+    pub fn map(&self, source: u64) -> Option<u64> {
+        source
+            .checked_sub(self.source)
+            .filter(|&n| n < self.length)
+            .and_then(|n| self.destination.checked_add(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parsing::parse_data,
+        solving::{part1, part2, tests::SAMPLE},
+    };
+
+    #[test]
+    fn odd_seed_lists() {
+        let almanac = parse_data(&SAMPLE.replacen("79 14 55 13", "79 14 55", 1)).unwrap();
+
+        assert_eq!(almanac.individual_seeds(), [79, 14, 55]);
+        assert_eq!(part1(&almanac), 43);
+        assert_eq!(almanac.seed_ranges(), Err(SeedRangeError::OddCount(3)));
+        assert_eq!(part2(&almanac), Err(SeedRangeError::OddCount(3)));
+    }
+
+    #[test]
+    fn seed_range_overflow() {
+        let seeds = format!("79 14 {} 2", u64::MAX - 1);
+        let almanac = parse_data(&SAMPLE.replacen("79 14 55 13", &seeds, 1)).unwrap();
+
+        assert_eq!(
+            almanac.seed_ranges(),
+            Err(SeedRangeError::Overflow {
+                start: u64::MAX - 1,
+                len: 2
+            })
+        );
+        assert_eq!(
+            part2(&almanac),
+            Err(SeedRangeError::Overflow {
+                start: u64::MAX - 1,
+                len: 2
+            })
+        );
+
+        // Ending exactly at `u64::MAX` is fine
+        let seeds = format!("{} 1", u64::MAX - 1);
+        let almanac = parse_data(&SAMPLE.replacen("79 14 55 13", &seeds, 1)).unwrap();
+        assert_eq!(almanac.seed_ranges().unwrap()[0], u64::MAX - 1..u64::MAX);
+    }
+
+    #[test]
+    fn map_matches_map_linear() {
+        let ranges = [
+            // Overlapping, the first one wins
+            (100, 10, 10),
+            (200, 15, 10),
+            // Adjacent
+            (300, 30, 5),
+            (0, 35, 5),
+            // Zero length
+            (400, 50, 0),
+            (500, 40, 0),
+            (50, 60, 20),
+            (u64::MAX - 3, 90, 4),
+            (7, u64::MAX - 2, 2),
+        ]
+        .map(|(destination, source, length)| MapRange {
+            destination,
+            source,
+            length,
+        });
+        let mapper = Mapper::new("seed".to_owned(), "soil".to_owned(), ranges.to_vec());
+
+        for value in (0..200).chain(u64::MAX - 5..=u64::MAX) {
+            assert_eq!(mapper.map(value), mapper.map_linear(value), "{value}");
+        }
+    }
+}
@@ -1,38 +1,78 @@
-mod parsing;
-mod solving;
+use std::{env, fmt::Display, process};
 
-use solving::{part1, part2};
+use day5::{
+    parsing,
+    solving::{part1, part2},
+    validation::Strictness,
+    writer::Simplification,
+};
 
 static INPUT: &str = include_str!("input.txt");
 
 fn main() {
     let parsed = parsing::parse_data(INPUT).unwrap();
+    or_exit(parsed.validate_categories());
+
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    match args.as_slice() {
+        ["map", from, to, value] => {
+            println!("{}", or_exit(parsed.map(from, to, value.parse().unwrap())));
+            return;
+        }
+        ["svg"] => {
+            let flow = parsed.flow(&or_exit(parsed.seed_ranges())).unwrap();
+            print!("{}", flow.to_svg());
+            return;
+        }
+        ["trace", seed, format @ ..] => {
+            let trace = parsed.trace(seed.parse().unwrap()).unwrap();
+
+            match format {
+                [] => print!("{trace}"),
+                ["json"] => println!("{}", trace.to_json().unwrap()),
+                _ => panic!("Usage: trace <seed> [json]"),
+            }
+
+            return;
+        }
+        ["write", simplification @ ..] => {
+            let simplification = match simplification {
+                [] => Simplification::Verbatim,
+                ["normalised"] => Simplification::Normalised,
+                ["collapsed"] => Simplification::Collapsed,
+                _ => panic!("Usage: write [normalised|collapsed]"),
+            };
+
+            println!("{}", parsed.to_text(simplification));
+            return;
+        }
+        ["validate", strictness @ ..] => {
+            let strictness = match strictness {
+                [] => Strictness::Lenient,
+                ["strict"] => Strictness::Strict,
+                _ => panic!("Usage: validate [strict]"),
+            };
+
+            print!("{}", or_exit(parsed.validate(strictness)));
+            return;
+        }
+        _ => {}
+    }
+
     let part1 = part1(&parsed);
 
     println!("{part1}");
 
-    let part2 = part2(&parsed);
+    let part2 = or_exit(part2(&parsed));
     println!("{part2}");
 }
 
-#[derive(Debug)]
-pub struct Almanac {
-    pub seeds: Vec<(u32, u32)>,
-    pub mappers: Vec<Vec<MapRange>>,
-}
-
-#[derive(Debug)]
-pub struct MapRange {
-    pub destination: u32,
-    pub source: u32,
-    pub length: u32,
-}
-
-impl MapRange {
-    pub fn map(&self, source: u32) -> Option<u32> {
-        source
-            .checked_sub(self.source)
-            .filter(|&n| n < self.length)
-            .map(|n| self.destination + n)
-    }
+/// Reports errors in the almanac, e.g. seed ranges only needed by some commands, without a backtrace
+fn or_exit<T>(result: Result<T, impl Display>) -> T {
+    result.unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(1)
+    })
 }
@@ -1,3 +1,5 @@
+use std::{error::Error, fmt::Display};
+
 use nom::{
     bytes::complete::tag,
     character::{
@@ -5,32 +7,97 @@ use nom::{
         streaming::space0,
     },
     combinator::{all_consuming, map_res},
-    multi::{many1, separated_list1},
+    multi::{many0, many1, separated_list1},
     sequence::{pair, preceded, tuple},
     IResult,
 };
 
-use crate::{Almanac, MapRange};
+use crate::{Almanac, MapRange, Mapper};
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError<'a> {
+    Syntax(nom::Err<nom::error::Error<&'a str>>),
+    /// Range `index` of the `source`-to-`destination` map ends past `u64::MAX`
+    RangeOverflow {
+        source: String,
+        destination: String,
+        index: usize,
+        range: MapRange,
+    },
+}
+
+impl Display for ParseError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Syntax(error) => write!(f, "Invalid almanac: {error}"),
+            ParseError::RangeOverflow {
+                source,
+                destination,
+                index,
+                range,
+            } => write!(
+                f,
+                "Range {index} of the {source}-to-{destination} map, {} {} {}, goes past u64::MAX",
+                range.destination, range.source, range.length
+            ),
+        }
+    }
+}
+
+impl Error for ParseError<'_> {}
+
+impl<'a> From<nom::Err<nom::error::Error<&'a str>>> for ParseError<'a> {
+    fn from(error: nom::Err<nom::error::Error<&'a str>>) -> Self {
+        ParseError::Syntax(error)
+    }
+}
 
-pub fn parse_data(input: &str) -> Result<Almanac, nom::Err<nom::error::Error<&str>>> {
+/// Fails on ranges which would end past `u64::MAX`
+pub fn parse_data(input: &str) -> Result<Almanac, ParseError<'_>> {
     let (rest, seeds) = parse_seeds(input)?;
 
     let (_, mappers) = all_consuming(many1(preceded(pair(newline, newline), parse_mapper)))(rest)?;
 
+    // Checked before building the mappers, whose tables rely on ranges ending by `u64::MAX`
+    for (source, destination, ranges) in &mappers {
+        if let Some((index, &range)) = ranges.iter().enumerate().find(|(_, range)| {
+            range.destination.checked_add(range.length).is_none()
+                || range.source.checked_add(range.length).is_none()
+        }) {
+            return Err(ParseError::RangeOverflow {
+                source: source.to_string(),
+                destination: destination.to_string(),
+                index,
+                range,
+            });
+        }
+    }
+
+    let mappers = mappers
+        .into_iter()
+        .map(|(source, destination, ranges)| {
+            Mapper::new(source.to_owned(), destination.to_owned(), ranges)
+        })
+        .collect();
+
     Ok(Almanac { seeds, mappers })
 }
 
-fn parse_seeds(i: &str) -> IResult<&str, Vec<(u32, u32)>> {
+fn parse_seeds(i: &str) -> IResult<&str, Vec<u64>> {
     preceded(
         tuple((tag("seeds:"), space0)),
-        separated_list1(space1, number_pair),
+        separated_list1(space1, number),
     )(i)
 }
 
-fn parse_mapper(i: &str) -> IResult<&str, Vec<MapRange>> {
-    let (rest, _) = tuple((alpha1, tag("-to-"), alpha1, space1, tag("map:"), newline))(i)?;
+/// Source and destination categories with the ranges of the map, which can have none
+fn parse_mapper(i: &str) -> IResult<&str, (&str, &str, Vec<MapRange>)> {
+    let (rest, (source, _, destination, _, _)) =
+        tuple((alpha1, tag("-to-"), alpha1, space1, tag("map:")))(i)?;
+
+    let (rest, ranges) = many0(preceded(newline, parse_mapper_range))(rest)?;
 
-    separated_list1(newline, parse_mapper_range)(rest)
+    Ok((rest, (source, destination, ranges)))
 }
 
 fn parse_mapper_range(i: &str) -> IResult<&str, MapRange> {
@@ -47,12 +114,39 @@ fn parse_mapper_range(i: &str) -> IResult<&str, MapRange> {
     ))
 }
 
-fn number_pair(i: &str) -> IResult<&str, (u32, u32)> {
-    let (rest, (x, _, y)) = tuple((number, space1, number))(i)?;
-
-    Ok((rest, (x, y)))
+fn number(i: &str) -> IResult<&str, u64> {
+    map_res(digit1, |s: &str| s.parse())(i)
 }
 
-fn number(i: &str) -> IResult<&str, u32> {
-    map_res(digit1, |s: &str| s.parse())(i)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_overflow() {
+        let input = format!(
+            "seeds: 1 2\n\nseed-to-soil map:\n0 0 1\n{} 0 2",
+            u64::MAX - 1
+        );
+
+        assert_eq!(
+            parse_data(&input).unwrap_err(),
+            ParseError::RangeOverflow {
+                source: "seed".to_owned(),
+                destination: "soil".to_owned(),
+                index: 1,
+                range: MapRange {
+                    destination: u64::MAX - 1,
+                    source: 0,
+                    length: 2,
+                },
+            }
+        );
+
+        let input = format!("seeds: 1 2\n\nseed-to-soil map:\n0 {} 1", u64::MAX);
+        assert!(matches!(
+            parse_data(&input),
+            Err(ParseError::RangeOverflow { index: 0, .. })
+        ));
+    }
 }
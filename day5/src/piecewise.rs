@@ -0,0 +1,359 @@
+use std::ops::RangeInclusive;
+
+use crate::{categories::CategoryError, Almanac, MapRange, LOCATION, SEED};
+
+/// Values from `start` up to the next segment's start map to `destination` onwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: u64,
+    pub destination: u64,
+}
+
+impl Segment {
+    fn map(&self, value: u64) -> u64 {
+        self.destination + (value - self.start)
+    }
+}
+
+/// Mapping over every `u64`, split into sorted segments which are each shifted by some amount
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piecewise {
+    segments: Vec<Segment>,
+}
+
+impl Piecewise {
+    pub fn identity() -> Self {
+        Self {
+            segments: vec![Segment {
+                start: 0,
+                destination: 0,
+            }],
+        }
+    }
+
+    /// Builds a single mapper layer, where values outside every range map to themselves
+    ///
+    /// Like [`crate::Mapper::map_linear`], the first range containing a value is the one used.
+    pub fn from_ranges(ranges: &[MapRange]) -> Self {
+        let mut boundaries = ranges
+            .iter()
+            .flat_map(|range| [range.source, range.source + range.length])
+            .chain([0])
+            .collect::<Vec<_>>();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let segments = boundaries
+            .into_iter()
+            .map(|start| Segment {
+                start,
+                destination: ranges
+                    .iter()
+                    .find_map(|range| range.map(start))
+                    .unwrap_or(start),
+            })
+            .collect();
+
+        Self::merged(segments)
+    }
+
+    /// Joins neighbouring segments which continue the same shift
+    fn merged(segments: Vec<Segment>) -> Self {
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+
+        for segment in segments {
+            match merged.last() {
+                Some(last)
+                    if last.destination.checked_add(segment.start - last.start)
+                        == Some(segment.destination) => {}
+                _ => merged.push(segment),
+            }
+        }
+
+        Self { segments: merged }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Segments which don't map to themselves, as ranges of the puzzle input
+    pub fn ranges(&self) -> Vec<MapRange> {
+        self.segments
+            .iter()
+            .enumerate()
+            .filter(|(_, segment)| segment.start != segment.destination)
+            .map(|(i, segment)| MapRange {
+                destination: segment.destination,
+                source: segment.start,
+                length: self.end(i) - segment.start + 1,
+            })
+            .collect()
+    }
+
+    /// Inclusive end of the segment at `i`
+    fn end(&self, i: usize) -> u64 {
+        self.segments
+            .get(i + 1)
+            .map_or(u64::MAX, |next| next.start - 1)
+    }
+
+    fn segment_index(&self, value: u64) -> usize {
+        self.segments
+            .partition_point(|segment| segment.start <= value)
+            - 1
+    }
+
+    pub fn map(&self, value: u64) -> u64 {
+        self.segments[self.segment_index(value)].map(value)
+    }
+
+    /// Intervals `range` is mapped to, in the order of the values they came from
+    pub fn map_range(&self, range: RangeInclusive<u64>) -> Vec<RangeInclusive<u64>> {
+        let (&start, &end) = (range.start(), range.end());
+        let mut mapped = Vec::new();
+
+        for i in self.segment_index(start)..self.segments.len() {
+            let segment = self.segments[i];
+
+            if segment.start > end {
+                break;
+            }
+
+            let from = start.max(segment.start);
+            let to = end.min(self.end(i));
+
+            mapped.push(segment.map(from)..=segment.map(to));
+        }
+
+        mapped
+    }
+
+    /// Mapping which applies `self` and then `next`
+    pub fn then(&self, next: &Piecewise) -> Piecewise {
+        let mut segments = Vec::with_capacity(self.segments.len() + next.segments.len());
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let image_start = segment.destination;
+            let image_end = segment.map(self.end(i));
+
+            for j in next.segment_index(image_start)..next.segments.len() {
+                let next_segment = next.segments[j];
+
+                if next_segment.start > image_end {
+                    break;
+                }
+
+                let from = image_start.max(next_segment.start);
+
+                segments.push(Segment {
+                    start: segment.start + (from - image_start),
+                    destination: next_segment.map(from),
+                });
+            }
+        }
+
+        Self::merged(segments)
+    }
+
+    pub fn inverse(&self) -> Inverse {
+        let images = self
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| (segment.destination, segment.map(self.end(i)), segment.start))
+            .collect::<Vec<_>>();
+
+        let mut boundaries = images
+            .iter()
+            .flat_map(|&(start, end, _)| [Some(start), end.checked_add(1)])
+            .flatten()
+            .chain([0])
+            .collect::<Vec<_>>();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let pieces = boundaries
+            .into_iter()
+            .map(|start| InversePiece {
+                start,
+                sources: images
+                    .iter()
+                    .filter(|&&(image_start, image_end, _)| {
+                        (image_start..=image_end).contains(&start)
+                    })
+                    .map(|&(image_start, _, source)| Segment {
+                        start: image_start,
+                        destination: source,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Inverse { pieces }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct InversePiece {
+    start: u64,
+    /// Segments mapping this piece back to seeds, a location can come from many seeds or none
+    sources: Vec<Segment>,
+}
+
+/// Lookup from locations back to the seeds which end up there
+#[derive(Debug, Clone)]
+pub struct Inverse {
+    pieces: Vec<InversePiece>,
+}
+
+impl Inverse {
+    fn piece_index(&self, value: u64) -> usize {
+        self.pieces.partition_point(|piece| piece.start <= value) - 1
+    }
+
+    fn end(&self, i: usize) -> u64 {
+        self.pieces
+            .get(i + 1)
+            .map_or(u64::MAX, |next| next.start - 1)
+    }
+
+    pub fn preimage(&self, location: u64) -> Vec<u64> {
+        self.pieces[self.piece_index(location)]
+            .sources
+            .iter()
+            .map(|source| source.map(location))
+            .collect()
+    }
+
+    pub fn preimage_range(&self, range: RangeInclusive<u64>) -> Vec<RangeInclusive<u64>> {
+        let (&start, &end) = (range.start(), range.end());
+        let mut seeds = Vec::new();
+
+        for i in self.piece_index(start)..self.pieces.len() {
+            let piece = &self.pieces[i];
+
+            if piece.start > end {
+                break;
+            }
+
+            let from = start.max(piece.start);
+            let to = end.min(self.end(i));
+
+            seeds.extend(
+                piece
+                    .sources
+                    .iter()
+                    .map(|source| source.map(from)..=source.map(to)),
+            );
+        }
+
+        seeds.sort_unstable_by_key(|seeds| *seeds.start());
+        seeds
+    }
+}
+
+impl Almanac {
+    /// Every mapper layer from seed to location combined into one mapping
+    ///
+    /// Panics unless the almanac passed [`Almanac::validate_categories`]
+    pub fn compose(&self) -> Piecewise {
+        self.compose_between(SEED, LOCATION)
+            .expect("validate_categories checks seed maps to location")
+    }
+
+    pub fn compose_between(&self, from: &str, to: &str) -> Result<Piecewise, CategoryError> {
+        Ok(self
+            .path(from, to)?
+            .into_iter()
+            .map(|mapper| &mapper.table)
+            .fold(Piecewise::identity(), |composed, layer| {
+                composed.then(layer)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parsing::parse_data,
+        solving::{part1, part1_composed, part2, part2_composed, tests::SAMPLE},
+    };
+
+    fn layer(ranges: &[(u64, u64, u64)]) -> Piecewise {
+        Piecewise::from_ranges(
+            &ranges
+                .iter()
+                .map(|&(destination, source, length)| MapRange {
+                    destination,
+                    source,
+                    length,
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn then_maps_through_both_layers() {
+        let first = layer(&[(50, 98, 2), (52, 50, 48), (0, 10, 5)]);
+        let second = layer(&[(0, 15, 37), (37, 52, 2), (39, 0, 15), (1000, 99, 1)]);
+        let composed = first.then(&second);
+
+        for value in (0..200).chain(u64::MAX - 5..=u64::MAX) {
+            assert_eq!(composed.map(value), second.map(first.map(value)), "{value}");
+        }
+
+        assert_eq!(Piecewise::identity().then(&first), first);
+        assert_eq!(first.then(&Piecewise::identity()), first);
+    }
+
+    #[test]
+    fn inverse_finds_every_seed() {
+        let almanac = parse_data(SAMPLE).unwrap();
+        let composed = almanac.compose();
+        let inverse = composed.inverse();
+
+        // The sample only maps values below 100, everything above maps to itself
+        let seeds = |locations: RangeInclusive<u64>| {
+            (0..300)
+                .filter(|&seed| locations.contains(&composed.map(seed)))
+                .collect::<Vec<_>>()
+        };
+
+        for location in 0..200 {
+            let mut preimage = inverse.preimage(location);
+            preimage.sort_unstable();
+            assert_eq!(preimage, seeds(location..=location), "{location}");
+        }
+
+        for (start, end) in [(0, 10), (40, 60), (46, 46), (90, 150)] {
+            let mut preimage = inverse
+                .preimage_range(start..=end)
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            preimage.sort_unstable();
+            assert_eq!(preimage, seeds(start..=end), "{start}..={end}");
+        }
+    }
+
+    #[test]
+    fn composed_solvers_match() {
+        for input in [SAMPLE, include_str!("input.txt")] {
+            let almanac = parse_data(input).unwrap();
+            let composed = almanac.compose();
+
+            assert_eq!(part1_composed(&almanac, &composed), part1(&almanac));
+            assert_eq!(part2_composed(&almanac, &composed), part2(&almanac));
+
+            // The lowest location comes from at least one seed inside the seed ranges
+            let seed_ranges = almanac.seed_ranges().unwrap();
+            assert!(composed
+                .inverse()
+                .preimage(part2(&almanac).unwrap())
+                .iter()
+                .any(|seed| seed_ranges.iter().any(|seeds| seeds.contains(seed))));
+        }
+    }
+}
@@ -1,27 +1,74 @@
-use std::cmp::Reverse;
+use std::{cmp::Reverse, ops::Range};
 
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-use crate::{Almanac, MapRange};
+use crate::{piecewise::Piecewise, Almanac, MapRange, Mapper, SeedRangeError, LOCATION, SEED};
 
-pub fn part1(input: &Almanac) -> u32 {
+pub fn part1(input: &Almanac) -> u64 {
     generic_solver(
-        &input.mappers,
-        input.seeds.iter().flat_map(|(x, y)| [x, y]).copied(),
+        &seed_to_location(input),
+        input.individual_seeds().iter().copied(),
     )
 }
 
-pub fn part2(input: &Almanac) -> u32 {
+/// Pushes whole seed intervals through every mapper, splitting them where the ranges start and end
+pub fn part2(input: &Almanac) -> Result<u64, SeedRangeError> {
+    let seeds = input
+        .seed_ranges()?
+        .into_iter()
+        .filter(|seeds| !seeds.is_empty())
+        .collect();
+
+    let locations = seed_to_location(input)
+        .into_iter()
+        .fold(seeds, |intervals, mapper| {
+            map_intervals(&mapper.ranges, intervals)
+        });
+
+    locations
+        .into_iter()
+        .map(|location| location.start)
+        .min()
+        .ok_or(SeedRangeError::Empty)
+}
+
+/// Maps every seed one by one, kept as a reference for [`part2`]
+pub fn part2_brute_force(input: &Almanac) -> Result<u64, SeedRangeError> {
     par_generic_solver(
-        &input.mappers,
-        input
-            .seeds
-            .par_iter()
-            .flat_map(|&(start, len)| start..start + len),
+        &seed_to_location(input),
+        input.seed_ranges()?.into_par_iter().flatten(),
     )
+    .ok_or(SeedRangeError::Empty)
+}
+
+pub fn part1_composed(input: &Almanac, composed: &Piecewise) -> u64 {
+    input
+        .individual_seeds()
+        .iter()
+        .map(|&seed| composed.map(seed))
+        .min()
+        .unwrap()
+}
+
+pub fn part2_composed(input: &Almanac, composed: &Piecewise) -> Result<u64, SeedRangeError> {
+    input
+        .seed_ranges()?
+        .into_iter()
+        .filter(|seeds| !seeds.is_empty())
+        .flat_map(|seeds| composed.map_range(seeds.start..=seeds.end - 1))
+        .map(|location| *location.start())
+        .min()
+        .ok_or(SeedRangeError::Empty)
+}
+
+/// Panics unless the almanac passed [`Almanac::validate_categories`], which every solver relies on
+fn seed_to_location(input: &Almanac) -> Vec<&Mapper> {
+    input
+        .path(SEED, LOCATION)
+        .expect("validate_categories checks seed maps to location")
 }
 
-fn generic_solver(mappers: &[Vec<MapRange>], seeds: impl Iterator<Item = u32>) -> u32 {
+fn generic_solver(mappers: &[&Mapper], seeds: impl Iterator<Item = u64>) -> u64 {
     let lowest_location = seeds
         .map(|seed| map_over_maps(mappers, seed))
         .fold(None, |lowest_location, location| {
@@ -31,17 +78,112 @@ fn generic_solver(mappers: &[Vec<MapRange>], seeds: impl Iterator<Item = u32>) -
     lowest_location.unwrap().0
 }
 
-fn par_generic_solver(mappers: &[Vec<MapRange>], seeds: impl ParallelIterator<Item = u32>) -> u32 {
+fn par_generic_solver(
+    mappers: &[&Mapper],
+    seeds: impl ParallelIterator<Item = u64>,
+) -> Option<u64> {
     seeds
         .map(|seed| map_over_maps(mappers, seed))
         .map(|n| Some(Reverse(n)))
         .reduce(|| None, |a, b| a.max(b))
-        .unwrap()
-        .0
+        .map(|lowest| lowest.0)
+}
+
+fn map_over_maps(mappers: &[&Mapper], seed: u64) -> u64 {
+    mappers.iter().fold(seed, |prev, mapper| mapper.map(prev))
+}
+
+fn map_intervals(mapper: &[MapRange], intervals: Vec<Range<u64>>) -> Vec<Range<u64>> {
+    let mut mapped = Vec::with_capacity(intervals.len());
+    let mut unmapped = intervals;
+
+    // Like `find_map` in `Mapper::map_linear`, the first range containing a value is the one used
+    for range in mapper {
+        let source = range.source..range.source + range.length;
+        let destination = range.destination;
+
+        let mut rest = Vec::with_capacity(unmapped.len());
+
+        for interval in unmapped {
+            let before = interval.start..interval.end.min(source.start);
+            let inside = interval.start.max(source.start)..interval.end.min(source.end);
+            let after = interval.start.max(source.end)..interval.end;
+
+            if !inside.is_empty() {
+                mapped.push(
+                    inside.start - source.start + destination
+                        ..inside.end - source.start + destination,
+                );
+            }
+
+            rest.extend([before, after].into_iter().filter(|r| !r.is_empty()));
+        }
+
+        unmapped = rest;
+    }
+
+    mapped.extend(unmapped);
+    mapped
 }
 
-fn map_over_maps(mappers: &[Vec<MapRange>], seed: u32) -> u32 {
-    mappers.iter().fold(seed, |prev, map| {
-        map.iter().find_map(|range| range.map(prev)).unwrap_or(prev)
-    })
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::parsing::parse_data;
+
+    pub(crate) const SAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    #[test]
+    fn sample() {
+        let almanac = parse_data(SAMPLE).unwrap();
+
+        assert_eq!(part1(&almanac), 35);
+        assert_eq!(part2(&almanac), Ok(46));
+        assert_eq!(part2_brute_force(&almanac), Ok(46));
+    }
+
+    #[test]
+    fn empty_seed_ranges() {
+        let almanac = parse_data("seeds: 4 0\n\nseed-to-location map:\n0 1 2").unwrap();
+
+        assert_eq!(part2(&almanac), Err(SeedRangeError::Empty));
+        assert_eq!(part2_brute_force(&almanac), Err(SeedRangeError::Empty));
+        assert_eq!(
+            part2_composed(&almanac, &almanac.compose()),
+            Err(SeedRangeError::Empty)
+        );
+    }
 }
@@ -0,0 +1,135 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::{categories::CategoryError, Almanac, MapRange, LOCATION, SEED};
+
+/// Value of a seed after every map on the way to its location
+#[derive(Debug, Clone, Serialize)]
+pub struct Trace {
+    pub seed: u64,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Step {
+    pub source: String,
+    pub destination: String,
+    pub from: u64,
+    pub to: u64,
+    /// Range the value was mapped by, `None` when it maps to itself
+    pub range: Option<MapRange>,
+}
+
+impl Trace {
+    pub fn location(&self) -> u64 {
+        self.steps.last().map_or(self.seed, |step| step.to)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{SEED} {}", self.seed)?;
+
+        for step in &self.steps {
+            write!(
+                f,
+                "{}-to-{}: {} -> {} ",
+                step.source, step.destination, step.from, step.to
+            )?;
+
+            match step.range {
+                Some(range) => writeln!(
+                    f,
+                    "({} {} {})",
+                    range.destination, range.source, range.length
+                )?,
+                None => writeln!(f, "(identity)")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Almanac {
+    pub fn trace(&self, seed: u64) -> Result<Trace, CategoryError> {
+        let mut value = seed;
+        let mut steps = Vec::new();
+
+        for mapper in self.path(SEED, LOCATION)? {
+            let range = mapper
+                .ranges
+                .iter()
+                .find(|range| range.map(value).is_some())
+                .copied();
+            let to = range.map_or(value, |range| range.map(value).unwrap());
+
+            steps.push(Step {
+                source: mapper.source.clone(),
+                destination: mapper.destination.clone(),
+                from: value,
+                to,
+                range,
+            });
+
+            value = to;
+        }
+
+        Ok(Trace { seed, steps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parsing::parse_data,
+        solving::{part1, tests::SAMPLE},
+    };
+
+    #[test]
+    fn sample_seed() {
+        let almanac = parse_data(SAMPLE).unwrap();
+        let trace = almanac.trace(79).unwrap();
+
+        let range = |destination, source, length| {
+            Some(MapRange {
+                destination,
+                source,
+                length,
+            })
+        };
+
+        assert_eq!(
+            trace
+                .steps
+                .iter()
+                .map(|step| (step.from, step.to, step.range))
+                .collect::<Vec<_>>(),
+            [
+                (79, 81, range(52, 50, 48)),
+                (81, 81, None),
+                (81, 81, None),
+                (81, 74, range(18, 25, 70)),
+                (74, 78, range(68, 64, 13)),
+                (78, 78, None),
+                (78, 82, range(60, 56, 37)),
+            ]
+        );
+        assert_eq!(trace.steps[0].source, SEED);
+        assert_eq!(trace.steps[6].destination, LOCATION);
+        assert_eq!(trace.location(), 82);
+
+        let lowest = almanac
+            .individual_seeds()
+            .iter()
+            .map(|&seed| almanac.trace(seed).unwrap().location())
+            .min();
+        assert_eq!(lowest, Some(part1(&almanac)));
+    }
+}
@@ -0,0 +1,263 @@
+use std::{error::Error, fmt::Display, ops::RangeInclusive};
+
+use crate::{Almanac, Mapper};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Strictness {
+    /// Overlapping ranges are only reported, the first one wins like in the solvers
+    #[default]
+    Lenient,
+    /// Overlapping ranges are an error
+    Strict,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlap {
+    /// Indices of the two ranges in their map
+    pub first: usize,
+    pub second: usize,
+    pub values: RangeInclusive<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LayerReport {
+    pub source: String,
+    pub destination: String,
+    pub overlaps: Vec<Overlap>,
+    /// Indices of ranges with a length of 0
+    pub empty: Vec<usize>,
+    /// Values no range covers, which map to themselves
+    pub gaps: Vec<RangeInclusive<u64>>,
+}
+
+impl LayerReport {
+    pub fn new(mapper: &Mapper) -> Self {
+        let sources = mapper
+            .ranges
+            .iter()
+            .map(|range| range.source..range.source + range.length)
+            .collect::<Vec<_>>();
+
+        let empty = (0..sources.len())
+            .filter(|&i| sources[i].is_empty())
+            .collect();
+
+        let mut overlaps = Vec::new();
+
+        for (first, x) in sources.iter().enumerate() {
+            for (second, y) in sources.iter().enumerate().skip(first + 1) {
+                let start = x.start.max(y.start);
+                let end = x.end.min(y.end);
+
+                if start < end {
+                    overlaps.push(Overlap {
+                        first,
+                        second,
+                        values: start..=end - 1,
+                    });
+                }
+            }
+        }
+
+        let mut covered = sources
+            .into_iter()
+            .filter(|source| !source.is_empty())
+            .collect::<Vec<_>>();
+        covered.sort_unstable_by_key(|source| source.start);
+
+        let mut gaps = Vec::new();
+        let mut next_uncovered = 0;
+
+        for source in covered {
+            if next_uncovered < source.start {
+                gaps.push(next_uncovered..=source.start - 1);
+            }
+
+            next_uncovered = next_uncovered.max(source.end);
+        }
+
+        // Ranges can't end past `u64::MAX` so it is never covered
+        gaps.push(next_uncovered..=u64::MAX);
+
+        Self {
+            source: mapper.source.clone(),
+            destination: mapper.destination.clone(),
+            overlaps,
+            empty,
+            gaps,
+        }
+    }
+}
+
+impl Display for LayerReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}-to-{} map:", self.source, self.destination)?;
+
+        for overlap in &self.overlaps {
+            writeln!(
+                f,
+                "  ranges {} and {} overlap on {}..={}, range {} is used",
+                overlap.first,
+                overlap.second,
+                overlap.values.start(),
+                overlap.values.end(),
+                overlap.first
+            )?;
+        }
+
+        for empty in &self.empty {
+            writeln!(f, "  range {empty} is empty")?;
+        }
+
+        for gap in &self.gaps {
+            writeln!(f, "  {}..={} maps to itself", gap.start(), gap.end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidationReport {
+    pub layers: Vec<LayerReport>,
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.layers
+            .iter()
+            .all(|layer| layer.overlaps.is_empty() && layer.empty.is_empty())
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for layer in &self.layers {
+            write!(f, "{layer}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlapError {
+    pub source: String,
+    pub destination: String,
+    pub overlap: Overlap,
+}
+
+impl Display for OverlapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Ranges {} and {} of the {}-to-{} map overlap on {}..={}",
+            self.overlap.first,
+            self.overlap.second,
+            self.source,
+            self.destination,
+            self.overlap.values.start(),
+            self.overlap.values.end()
+        )
+    }
+}
+
+impl Error for OverlapError {}
+
+impl Almanac {
+    pub fn validate(&self, strictness: Strictness) -> Result<ValidationReport, OverlapError> {
+        let layers = self
+            .mappers
+            .iter()
+            .map(LayerReport::new)
+            .collect::<Vec<_>>();
+
+        if strictness == Strictness::Strict {
+            if let Some((layer, overlap)) = layers
+                .iter()
+                .find_map(|layer| Some((layer, layer.overlaps.first()?)))
+            {
+                return Err(OverlapError {
+                    source: layer.source.clone(),
+                    destination: layer.destination.clone(),
+                    overlap: overlap.clone(),
+                });
+            }
+        }
+
+        Ok(ValidationReport { layers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parsing::parse_data, solving::tests::SAMPLE};
+
+    const MESSY: &str = "seeds: 1 2
+
+seed-to-location map:
+50 10 5
+60 12 5
+70 20 0
+80 30 2
+
+location-to-humidity map:
+0 18446744073709551610 5";
+
+    #[test]
+    fn sample_is_clean() {
+        let report = parse_data(SAMPLE)
+            .unwrap()
+            .validate(Strictness::Strict)
+            .unwrap();
+
+        assert!(report.is_clean());
+        assert_eq!(report.layers.len(), 7);
+    }
+
+    #[test]
+    fn messy_layers() {
+        let report = parse_data(MESSY)
+            .unwrap()
+            .validate(Strictness::Lenient)
+            .unwrap();
+        assert!(!report.is_clean());
+
+        let layer = &report.layers[0];
+        assert_eq!(
+            layer.overlaps,
+            [Overlap {
+                first: 0,
+                second: 1,
+                values: 12..=14
+            }]
+        );
+        assert_eq!(layer.empty, [2]);
+        assert_eq!(layer.gaps, [0..=9, 17..=29, 32..=u64::MAX]);
+
+        // The last range ends just before `u64::MAX`, which is still a gap
+        let layer = &report.layers[1];
+        assert!(layer.overlaps.is_empty() && layer.empty.is_empty());
+        assert_eq!(layer.gaps, [0..=u64::MAX - 6, u64::MAX..=u64::MAX]);
+    }
+
+    #[test]
+    fn strict_overlaps() {
+        assert_eq!(
+            parse_data(MESSY)
+                .unwrap()
+                .validate(Strictness::Strict)
+                .err(),
+            Some(OverlapError {
+                source: "seed".to_owned(),
+                destination: "location".to_owned(),
+                overlap: Overlap {
+                    first: 0,
+                    second: 1,
+                    values: 12..=14
+                }
+            })
+        );
+    }
+}
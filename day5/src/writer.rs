@@ -0,0 +1,105 @@
+use std::fmt::Write;
+
+use crate::{Almanac, LOCATION, SEED};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Simplification {
+    /// Every map and range as they were parsed
+    #[default]
+    Verbatim,
+    /// Every map written from its normalised table, so adjacent ranges with the same shift are merged,
+    /// overlaps are resolved and ranges mapping values to themselves are removed
+    Normalised,
+    /// All maps from seed to location composed into a single normalised `seed-to-location` map
+    Collapsed,
+}
+
+impl Almanac {
+    /// Writes the almanac in the puzzle's text format, which [`crate::parsing::parse_data`] reads back
+    pub fn to_text(&self, simplification: Simplification) -> String {
+        let layers = match simplification {
+            Simplification::Verbatim => self
+                .mappers
+                .iter()
+                .map(|mapper| (&*mapper.source, &*mapper.destination, mapper.ranges.clone()))
+                .collect::<Vec<_>>(),
+            Simplification::Normalised => self
+                .mappers
+                .iter()
+                .map(|mapper| (&*mapper.source, &*mapper.destination, mapper.table.ranges()))
+                .collect(),
+            Simplification::Collapsed => vec![(SEED, LOCATION, self.compose().ranges())],
+        };
+
+        let mut text = String::from("seeds:");
+
+        for seed in &self.seeds {
+            write!(text, " {seed}").unwrap();
+        }
+
+        // Maps which normalise to nothing are still written, without ranges, to keep the chain of categories
+        for (source, destination, ranges) in layers {
+            write!(text, "\n\n{source}-to-{destination} map:").unwrap();
+
+            for range in ranges {
+                write!(
+                    text,
+                    "\n{} {} {}",
+                    range.destination, range.source, range.length
+                )
+                .unwrap();
+            }
+        }
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parsing::parse_data, solving::tests::SAMPLE, validation::Strictness};
+
+    #[test]
+    fn verbatim_is_the_input() {
+        for input in [SAMPLE, include_str!("input.txt")] {
+            assert_eq!(
+                parse_data(input).unwrap().to_text(Simplification::Verbatim),
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn simplified_maps_the_same() {
+        for input in [SAMPLE, include_str!("input.txt")] {
+            let almanac = parse_data(input).unwrap();
+
+            for simplification in [Simplification::Normalised, Simplification::Collapsed] {
+                let simplified = parse_data(&almanac.to_text(simplification)).unwrap();
+
+                assert_eq!(simplified.seeds, almanac.seeds);
+                assert_eq!(simplified.compose(), almanac.compose());
+                assert!(simplified.validate(Strictness::Strict).unwrap().is_clean());
+            }
+        }
+    }
+
+    #[test]
+    fn identity_maps_keep_their_header() {
+        let almanac =
+            parse_data("seeds: 1 2\n\nseed-to-soil map:\n10 10 5\n\nsoil-to-location map:\n0 1 1")
+                .unwrap();
+        let text = almanac.to_text(Simplification::Normalised);
+
+        assert_eq!(
+            text,
+            "seeds: 1 2\n\nseed-to-soil map:\n\nsoil-to-location map:\n0 1 1"
+        );
+
+        let simplified = parse_data(&text).unwrap();
+        assert!(simplified.mappers[0].ranges.is_empty());
+        assert!(simplified.validate(Strictness::Strict).unwrap().is_clean());
+        assert_eq!(simplified.compose(), almanac.compose());
+    }
+}
@@ -0,0 +1,277 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{model::RaceModel, parsing::Race};
+
+/// Most hold times [`RaceModel::scan_record_for`] tries, every step of its search tries them all
+pub const SCAN_LIMIT: u128 = 10_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordError {
+    Impossible {
+        time: u128,
+        wins: u128,
+    },
+    /// The model has no closed form and the race has more than [`SCAN_LIMIT`] hold times
+    TooLongToScan {
+        time: u128,
+    },
+}
+
+impl Display for RecordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordError::Impossible { time, wins } => write!(
+                f,
+                "No record gives exactly {wins} winning hold times in a {time} ms race"
+            ),
+            RecordError::TooLongToScan { time } => write!(
+                f,
+                "A {time} ms race has too many hold times to scan, at most {SCAN_LIMIT} can be"
+            ),
+        }
+    }
+}
+
+impl Error for RecordError {}
+
+impl RaceModel {
+    /// Record which exactly `wins` hold times beat, the distance of the best hold time that loses
+    pub fn record_for(&self, time: u128, wins: u128) -> Result<u128, RecordError> {
+        let impossible = RecordError::Impossible { time, wins };
+
+        if !self.is_quadratic() {
+            return self.scan_record_for(time, wins);
+        }
+
+        // Without charging every distance is 0, which only a record of 0 or more stops from winning
+        if self.charge_rate == 0 {
+            return if wins == 0 { Ok(0) } else { Err(impossible) };
+        }
+
+        // Winning holds are symmetric around the peak, so there are `available - 2 * first + 1` of them
+        let available = time.saturating_sub(self.start_delay);
+        let optimal = self.optimal_hold(time);
+
+        if wins == 0 {
+            return self.distance(time, optimal).ok_or(impossible);
+        }
+
+        // Holding for the whole race or not at all never wins
+        if wins > available.saturating_sub(1) || !(available + 1 - wins).is_multiple_of(2) {
+            return Err(impossible);
+        }
+
+        let first = (available + 1 - wins) / 2;
+        self.distance(time, first - 1).ok_or(impossible)
+    }
+
+    /// Binary searches [`RaceModel::scan_count_wins`], which only goes down as the record goes up
+    ///
+    /// Works for any model but only up to [`SCAN_LIMIT`] hold times.
+    pub fn scan_record_for(&self, time: u128, wins: u128) -> Result<u128, RecordError> {
+        let impossible = RecordError::Impossible { time, wins };
+
+        if time >= SCAN_LIMIT {
+            return Err(RecordError::TooLongToScan { time });
+        }
+
+        // Only distances too big for a `u128` beat the longest one that fits
+        let mut high = (0..=time)
+            .filter_map(|hold| self.distance(time, hold))
+            .max()
+            .unwrap_or(0);
+        let mut low = 0;
+
+        if self.scan_count_wins(time, high) > wins {
+            return Err(impossible);
+        }
+
+        // Lowest record which at most `wins` hold times beat
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            if self.scan_count_wins(time, mid) <= wins {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        // Several hold times tie on the record and the count skips over `wins`
+        if self.scan_count_wins(time, low) != wins {
+            return Err(impossible);
+        }
+
+        Ok(low)
+    }
+}
+
+/// Races in the puzzle's input format, with every column lined up
+pub fn write_input(races: &[Race]) -> String {
+    let widths = races
+        .iter()
+        .map(|race| {
+            race.time
+                .to_string()
+                .len()
+                .max(race.record.to_string().len())
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        "{}\n{}\n",
+        row("Time:", races.iter().map(|race| race.time), &widths),
+        row("Distance:", races.iter().map(|race| race.record), &widths)
+    )
+}
+
+fn row(label: &str, numbers: impl Iterator<Item = u128>, widths: &[usize]) -> String {
+    numbers
+        .zip(widths)
+        .fold(format!("{label:<9}"), |row, (number, width)| {
+            row + &format!("   {number:>width$}")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{parse, Kerning};
+
+    #[test]
+    fn records_give_the_wins() {
+        for start_delay in 0..3 {
+            for charge_rate in 0..3 {
+                let model = RaceModel {
+                    charge_rate,
+                    start_delay,
+                    ..RaceModel::default()
+                };
+
+                for time in 0..30 {
+                    for wins in 0..35 {
+                        let record = model.record_for(time, wins);
+                        let scanned = model.scan_record_for(time, wins);
+
+                        assert_eq!(record.is_ok(), scanned.is_ok(), "{model:?} {time} {wins}");
+
+                        for record in [record, scanned].into_iter().flatten() {
+                            assert_eq!(model.count_wins(time, record), wins);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scanned_records_give_the_wins() {
+        let models = [
+            RaceModel {
+                friction: 1,
+                ..RaceModel::default()
+            },
+            RaceModel {
+                charge_rate: 2,
+                friction: 3,
+                start_delay: 1,
+                ..RaceModel::default()
+            },
+            RaceModel {
+                max_speed: Some(4),
+                ..RaceModel::default()
+            },
+            RaceModel {
+                charge_rate: 3,
+                max_speed: Some(7),
+                friction: 1,
+                ..RaceModel::default()
+            },
+        ];
+
+        for model in models {
+            assert!(!model.is_quadratic());
+
+            for time in 0..25 {
+                let counts = (0..=time * time * 3 + 1)
+                    .map(|record| model.count_wins(time, record))
+                    .collect::<Vec<_>>();
+
+                for wins in 0..30 {
+                    // The lowest record any hold time loses to which gives exactly `wins`
+                    let expected = counts
+                        .iter()
+                        .position(|&count| count == wins)
+                        .map(|record| record as u128)
+                        .ok_or(RecordError::Impossible { time, wins });
+
+                    assert_eq!(
+                        model.record_for(time, wins),
+                        expected,
+                        "{model:?} {time} {wins}"
+                    );
+                }
+            }
+        }
+
+        // Capped at a speed of 4 the distances are 0 8 14 18 20 16 12 8 4 0, holding 1 and 7 tie
+        let capped = models[2];
+        assert_eq!(capped.record_for(9, 3), Ok(14));
+        assert_eq!(capped.record_for(9, 5), Ok(8));
+        assert_eq!(
+            capped.record_for(9, 6),
+            Err(RecordError::Impossible { time: 9, wins: 6 })
+        );
+    }
+
+    #[test]
+    fn impossible_records() {
+        let model = RaceModel::default();
+
+        assert_eq!(
+            model.record_for(7, 3),
+            Err(RecordError::Impossible { time: 7, wins: 3 })
+        );
+        assert_eq!(
+            model.record_for(7, 7),
+            Err(RecordError::Impossible { time: 7, wins: 7 })
+        );
+
+        let friction = RaceModel {
+            friction: 1,
+            ..RaceModel::default()
+        };
+        assert_eq!(
+            friction.record_for(SCAN_LIMIT, 1),
+            Err(RecordError::TooLongToScan { time: SCAN_LIMIT })
+        );
+    }
+
+    #[test]
+    fn written_input_parses_back() {
+        let model = RaceModel::default();
+        let specs = [
+            (7, 4),
+            (15, 8),
+            (30, 9),
+            (62737565, 36872656),
+            (1 << 60, 1),
+            ((1 << 62) + 1, 1 << 40),
+        ];
+
+        let races = specs.map(|(time, wins)| Race {
+            time,
+            record: model.record_for(time, wins).unwrap(),
+        });
+        let parsed = parse(&write_input(&races), Kerning::Separate).unwrap();
+
+        assert_eq!(parsed, races);
+        assert_eq!(
+            parsed
+                .iter()
+                .map(|race| model.count_wins(race.time, race.record))
+                .collect::<Vec<_>>(),
+            specs.map(|(_, wins)| wins)
+        );
+    }
+}
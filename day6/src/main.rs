@@ -1,24 +1,76 @@
-mod part1;
-mod part2;
+mod generator;
+mod model;
+mod parsing;
+mod report;
+mod solver;
+
+use std::{env, process};
+
+use generator::{write_input, RecordError};
+use model::RaceModel;
+use parsing::{parse, Kerning, Race};
+use report::Report;
 
 static INPUT: &str = include_str!("input.txt");
 
 fn main() {
-    let part1: usize = part1::parse(INPUT)
-        .unwrap()
-        .into_iter()
-        .map(count_farther_then_best)
+    let model = RaceModel::default();
+
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    if let ["generate", specs @ ..] = args.as_slice() {
+        let races = specs
+            .iter()
+            .map(|spec| {
+                let (time, wins) = spec
+                    .split_once(':')
+                    .and_then(|(time, wins)| Some((time.parse().ok()?, wins.parse().ok()?)))
+                    .expect("Usage: generate <time:wins>...");
+
+                Ok(Race {
+                    time,
+                    record: model.record_for(time, wins)?,
+                })
+            })
+            .collect::<Result<Vec<_>, RecordError>>();
+
+        match races {
+            Ok(races) => print!("{}", write_input(&races)),
+            Err(error) => {
+                eprintln!("{error}");
+                process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    if let ["plan", rest @ ..] = args.as_slice() {
+        let (kerning, format) = match rest {
+            ["joined", format @ ..] => (Kerning::Joined, format),
+            format => (Kerning::Separate, format),
+        };
+        let report = Report::new(&parse(INPUT, kerning).unwrap(), &model);
+
+        match format {
+            [] | ["table"] => print!("{}", report.to_table()),
+            ["json"] => println!("{}", report.to_json().unwrap()),
+            _ => panic!("Usage: plan [joined] [table|json]"),
+        }
+
+        return;
+    }
+
+    let races = parse(INPUT, Kerning::Separate).unwrap();
+    let part1: u128 = races
+        .iter()
+        .map(|race| model.count_wins(race.time, race.record))
         .product();
 
     println!("{part1}");
 
-    let part2 = count_farther_then_best(part2::parse(INPUT).unwrap());
+    let race = parse(INPUT, Kerning::Joined).unwrap()[0];
+    let part2 = model.count_wins(race.time, race.record);
     println!("{part2}");
 }
-
-pub fn count_farther_then_best((time, best_distance): (u64, u64)) -> usize {
-    (1..time)
-        .map(|velocity| velocity * (time - velocity))
-        .filter(|&distance| distance > best_distance)
-        .count()
-}
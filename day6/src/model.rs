@@ -0,0 +1,218 @@
+use std::{cmp::Reverse, ops::RangeInclusive};
+
+use crate::solver;
+
+/// How holding the button turns into distance
+///
+/// The boat can only be charged once the start delay is over, then it moves at the speed it was
+/// charged to and slows down by `friction` every millisecond, until it stops or the race ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaceModel {
+    /// Speed gained per millisecond the button is held
+    pub charge_rate: u128,
+    pub max_speed: Option<u128>,
+    /// Speed lost per millisecond of moving
+    pub friction: u128,
+    /// Milliseconds at the start of the race before the button can be held
+    pub start_delay: u128,
+}
+
+impl Default for RaceModel {
+    /// The puzzle's races, gaining one millimetre per millisecond of speed for every millisecond held
+    fn default() -> Self {
+        Self {
+            charge_rate: 1,
+            max_speed: None,
+            friction: 0,
+            start_delay: 0,
+        }
+    }
+}
+
+impl RaceModel {
+    /// Milliseconds left for holding and moving once the start delay is over
+    fn available(&self, time: u128) -> u128 {
+        time.saturating_sub(self.start_delay)
+    }
+
+    /// Without a speed cap or friction the distance is `charge_rate * hold * (time - hold)`
+    pub fn is_quadratic(&self) -> bool {
+        self.max_speed.is_none() && self.friction == 0
+    }
+
+    /// Distance travelled holding the button for `hold`, `None` if it doesn't fit in a `u128`
+    pub fn distance(&self, time: u128, hold: u128) -> Option<u128> {
+        let available = self.available(time);
+
+        if hold > available {
+            return Some(0);
+        }
+
+        let charged = hold.checked_mul(self.charge_rate);
+        let speed = match (charged, self.max_speed) {
+            (Some(speed), Some(max_speed)) => speed.min(max_speed),
+            (None, Some(max_speed)) => max_speed,
+            (speed, None) => speed?,
+        };
+        let moving = available - hold;
+
+        if self.friction == 0 {
+            return speed.checked_mul(moving);
+        }
+
+        // Milliseconds until the boat stops, it moves `speed`, `speed - friction`, ... in each of them
+        let steps = moving.min(speed.div_ceil(self.friction));
+        let slowdown = self
+            .friction
+            .checked_mul(steps * steps.saturating_sub(1) / 2)?;
+
+        Some(speed.checked_mul(steps)? - slowdown)
+    }
+
+    fn beats(&self, time: u128, record: u128, hold: u128) -> bool {
+        self.distance(time, hold)
+            .is_none_or(|distance| distance > record)
+    }
+
+    /// First and last hold times which beat `record`, `None` if no hold time does
+    pub fn winning_holds(&self, time: u128, record: u128) -> Option<RangeInclusive<u128>> {
+        if !self.is_quadratic() {
+            return self.scan_winning_holds(time, record);
+        }
+
+        if self.charge_rate == 0 {
+            return None;
+        }
+
+        // `charge_rate * x > record` exactly when `x > record / charge_rate`
+        solver::winning_holds(self.available(time), record / self.charge_rate)
+    }
+
+    /// Tries every hold time, which works for any model
+    pub fn scan_winning_holds(&self, time: u128, record: u128) -> Option<RangeInclusive<u128>> {
+        let mut winning = (0..=self.available(time)).filter(|&hold| self.beats(time, record, hold));
+
+        let first = winning.next()?;
+        let last = winning.next_back().unwrap_or(first);
+
+        Some(first..=last)
+    }
+
+    /// Hold time which travels the furthest, the earliest one when there is a tie
+    pub fn optimal_hold(&self, time: u128) -> u128 {
+        if self.is_quadratic() && self.charge_rate > 0 {
+            return self.available(time) / 2;
+        }
+
+        (0..=self.available(time))
+            .min_by_key(|&hold| Reverse(self.distance(time, hold).unwrap_or(u128::MAX)))
+            .unwrap()
+    }
+
+    pub fn count_wins(&self, time: u128, record: u128) -> u128 {
+        if !self.is_quadratic() {
+            return self.scan_count_wins(time, record);
+        }
+
+        self.winning_holds(time, record)
+            .map_or(0, |holds| holds.end() - holds.start() + 1)
+    }
+
+    /// Counts by trying every hold time, as a speed cap or friction could make some in between lose
+    pub fn scan_count_wins(&self, time: u128, record: u128) -> u128 {
+        (0..=self.available(time))
+            .filter(|&hold| self.beats(time, record, hold))
+            .count() as u128
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn models() -> Vec<RaceModel> {
+        let mut models = Vec::new();
+
+        for charge_rate in 0..3 {
+            for max_speed in [None, Some(0), Some(4)] {
+                for friction in 0..3 {
+                    for start_delay in 0..3 {
+                        models.push(RaceModel {
+                            charge_rate,
+                            max_speed,
+                            friction,
+                            start_delay,
+                        });
+                    }
+                }
+            }
+        }
+
+        models
+    }
+
+    /// Moves the boat one millisecond at a time
+    fn simulate(model: &RaceModel, time: u128, hold: u128) -> u128 {
+        let available = time.saturating_sub(model.start_delay);
+
+        if hold > available {
+            return 0;
+        }
+
+        let mut speed = (hold * model.charge_rate).min(model.max_speed.unwrap_or(u128::MAX));
+        let mut distance = 0;
+
+        for _ in hold..available {
+            distance += speed;
+            speed = speed.saturating_sub(model.friction);
+        }
+
+        distance
+    }
+
+    #[test]
+    fn distance_matches_simulation() {
+        for model in models() {
+            for time in 0..20 {
+                for hold in 0..=time {
+                    assert_eq!(
+                        model.distance(time, hold),
+                        Some(simulate(&model, time, hold)),
+                        "{model:?} {time} {hold}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn closed_form_matches_scan() {
+        for model in models().into_iter().filter(RaceModel::is_quadratic) {
+            for time in 0..30 {
+                for record in 0..60 {
+                    assert_eq!(
+                        model.count_wins(time, record),
+                        model.scan_count_wins(time, record),
+                        "{model:?} {time} {record}"
+                    );
+                    assert_eq!(
+                        model.winning_holds(time, record),
+                        model.scan_winning_holds(time, record),
+                        "{model:?} {time} {record}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sample() {
+        let model = RaceModel::default();
+        let ways =
+            [(7, 9), (15, 40), (30, 200)].map(|(time, record)| model.count_wins(time, record));
+
+        assert_eq!(ways, [4, 8, 9]);
+        assert_eq!(model.count_wins(71530, 940200), 71503);
+        assert_eq!(model.optimal_hold(7), 3);
+    }
+}
@@ -0,0 +1,195 @@
+use std::{error::Error, fmt::Display};
+
+use nom::{
+    bytes::complete::tag_no_case,
+    character::complete::{digit1, line_ending, multispace0, space0, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded, separated_pair, tuple},
+    IResult,
+};
+
+/// How the numbers in a row are read
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Kerning {
+    /// Every number is a separate race
+    #[default]
+    Separate,
+    /// The spaces are bad kerning and each row is a single number
+    Joined,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u128,
+    pub record: u128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Syntax(String),
+    RowLengths { times: usize, distances: usize },
+    TooBig(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Syntax(error) => write!(f, "Invalid races: {error}"),
+            ParseError::RowLengths { times, distances } => write!(
+                f,
+                "Input has {times} times but {distances} distances, they should be the same"
+            ),
+            ParseError::TooBig(number) => write!(f, "{number} doesn't fit in a u128"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn parse(i: &str, kerning: Kerning) -> Result<Vec<Race>, ParseError> {
+    let (_, (times, distances)) = rows(i).map_err(|error| ParseError::Syntax(error.to_string()))?;
+
+    let (times, distances) = match kerning {
+        Kerning::Separate => (numbers(&times)?, numbers(&distances)?),
+        Kerning::Joined => (vec![number(&times)?], vec![number(&distances)?]),
+    };
+
+    if times.len() != distances.len() {
+        return Err(ParseError::RowLengths {
+            times: times.len(),
+            distances: distances.len(),
+        });
+    }
+
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, record)| Race { time, record })
+        .collect())
+}
+
+fn rows(i: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+    all_consuming(delimited(
+        multispace0,
+        separated_pair(
+            preceded(pair(tag_no_case("Time:"), space0), digit_groups),
+            // The rows have to be on separate lines
+            tuple((space0, line_ending, multispace0)),
+            preceded(pair(tag_no_case("Distance:"), space0), digit_groups),
+        ),
+        multispace0,
+    ))(i)
+}
+
+fn digit_groups(i: &str) -> IResult<&str, Vec<&str>> {
+    separated_list1(space1, digit1)(i)
+}
+
+fn numbers(groups: &[&str]) -> Result<Vec<u128>, ParseError> {
+    groups.iter().map(|&group| number(&[group])).collect()
+}
+
+/// Reads the digits of every group as one number, without building a string of them first
+fn number(groups: &[&str]) -> Result<u128, ParseError> {
+    groups
+        .iter()
+        .flat_map(|group| group.bytes())
+        .try_fold(0u128, |n, digit| {
+            n.checked_mul(10)?.checked_add(u128::from(digit - b'0'))
+        })
+        .ok_or_else(|| ParseError::TooBig(groups.concat()))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const SAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200
+";
+
+    fn races(races: &[(u128, u128)]) -> Result<Vec<Race>, ParseError> {
+        Ok(races
+            .iter()
+            .map(|&(time, record)| Race { time, record })
+            .collect())
+    }
+
+    #[test]
+    fn sample() {
+        assert_eq!(
+            parse(SAMPLE, Kerning::Separate),
+            races(&[(7, 9), (15, 40), (30, 200)])
+        );
+        assert_eq!(parse(SAMPLE, Kerning::Joined), races(&[(71530, 940200)]));
+    }
+
+    #[test]
+    fn whitespace() {
+        for input in [
+            SAMPLE.trim_end(),
+            "\n  Time: 7 15 30\n\n\tdistance:\t9\t40 200 \n\n",
+            "time:7  15 30\r\nDistance:9 40  200",
+        ] {
+            assert_eq!(
+                parse(input, Kerning::Separate),
+                races(&[(7, 9), (15, 40), (30, 200)]),
+                "{input:?}"
+            );
+        }
+
+        for input in [
+            "Time: 7 15 30 Distance: 9 40 200",
+            "Time: 7 15 30\nDistance:",
+            "Time:\nDistance: 9",
+            "Time: 7 15,30\nDistance: 9 40 200",
+            "Time: 7 15 30\nDistance: 9 40 200\nTime: 1",
+        ] {
+            assert!(
+                matches!(parse(input, Kerning::Separate), Err(ParseError::Syntax(_))),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn row_lengths() {
+        assert_eq!(
+            parse("Time: 7 15 30\nDistance: 9 40", Kerning::Separate),
+            Err(ParseError::RowLengths {
+                times: 3,
+                distances: 2
+            })
+        );
+        assert_eq!(
+            parse("Time: 7 15 30\nDistance: 9 40", Kerning::Joined),
+            races(&[(71530, 940)])
+        );
+    }
+
+    #[test]
+    fn too_big() {
+        let max = u128::MAX.to_string();
+        assert_eq!(max.len(), 39);
+
+        assert_eq!(
+            parse(&format!("Time: {max}\nDistance: 1"), Kerning::Separate),
+            races(&[(u128::MAX, 1)])
+        );
+        assert_eq!(
+            parse(&format!("Time: {max}0\nDistance: 1"), Kerning::Separate),
+            Err(ParseError::TooBig(format!("{max}0")))
+        );
+
+        // Joined groups can overflow even though every group fits
+        let half = &max[..20];
+        assert_eq!(
+            parse(
+                &format!("Time: {half} {half}\nDistance: 1"),
+                Kerning::Joined
+            ),
+            Err(ParseError::TooBig(format!("{half}{half}")))
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::{model::RaceModel, parsing::Race};
+
+/// How every race can be won and by how much
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub races: Vec<RaceReport>,
+    /// Product of the ways to win every race, the puzzle's answer
+    pub product: u128,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RaceReport {
+    pub time: u128,
+    pub record: u128,
+    /// First and last hold times which beat the record, `None` if none do
+    pub first_win: Option<u128>,
+    pub last_win: Option<u128>,
+    pub ways: u128,
+    pub optimal_hold: u128,
+    /// `None` when the distance doesn't fit in a `u128`
+    pub max_distance: Option<u128>,
+    /// How much further than the record the optimal hold goes, `None` when no hold beats the
+    /// record or the distance doesn't fit in a `u128`
+    pub margin: Option<u128>,
+}
+
+impl Report {
+    pub fn new(races: &[Race], model: &RaceModel) -> Self {
+        let races = races
+            .iter()
+            .map(|&Race { time, record }| {
+                let winning = model.winning_holds(time, record);
+                let optimal_hold = model.optimal_hold(time);
+                let max_distance = model.distance(time, optimal_hold);
+
+                RaceReport {
+                    time,
+                    record,
+                    first_win: winning.as_ref().map(|holds| *holds.start()),
+                    last_win: winning.as_ref().map(|holds| *holds.end()),
+                    ways: model.count_wins(time, record),
+                    optimal_hold,
+                    max_distance,
+                    margin: max_distance
+                        .filter(|&distance| distance > record)
+                        .map(|distance| distance - record),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            product: races.iter().map(|race| race.ways).product(),
+            races,
+        }
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "{:>16} {:>20} {:>24} {:>16} {:>16} {:>20} {:>20}\n",
+            "Time", "Record", "Winning holds", "Ways", "Optimal hold", "Max distance", "Margin"
+        );
+
+        for race in &self.races {
+            let winning = match (race.first_win, race.last_win) {
+                (Some(first), Some(last)) => format!("{first}..={last}"),
+                _ => "-".to_owned(),
+            };
+            let max_distance = race
+                .max_distance
+                .map_or("overflow".to_owned(), |distance| distance.to_string());
+            let margin = match (race.margin, race.max_distance) {
+                (Some(margin), _) => margin.to_string(),
+                (None, Some(_)) => "-".to_owned(),
+                (None, None) => "overflow".to_owned(),
+            };
+
+            writeln!(
+                table,
+                "{:>16} {:>20} {winning:>24} {:>16} {:>16} {max_distance:>20} {margin:>20}",
+                race.time, race.record, race.ways, race.optimal_hold
+            )
+            .unwrap();
+        }
+
+        writeln!(
+            table,
+            "{:>16} {:>20} {:>24} {:>16}",
+            "Product", "", "", self.product
+        )
+        .unwrap();
+
+        table
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::{parse, tests::SAMPLE, Kerning};
+
+    /// Table cells by row, ignoring the column widths
+    fn cells(table: &str) -> Vec<Vec<&str>> {
+        table
+            .lines()
+            .map(|line| line.split_whitespace().collect())
+            .collect()
+    }
+
+    #[test]
+    fn sample_table() {
+        let races = parse(SAMPLE, Kerning::Separate).unwrap();
+        let report = Report::new(&races, &RaceModel::default());
+
+        assert_eq!(report.product, 288);
+        assert_eq!(
+            cells(&report.to_table())[1..],
+            [
+                vec!["7", "9", "2..=5", "4", "3", "12", "3"],
+                vec!["15", "40", "4..=11", "8", "7", "56", "16"],
+                vec!["30", "200", "11..=19", "9", "15", "225", "25"],
+                vec!["Product", "288"],
+            ]
+        );
+    }
+
+    #[test]
+    fn unwinnable_race() {
+        // The optimal hold only ties the record
+        let races = [Race {
+            time: 7,
+            record: 12,
+        }];
+        let report = Report::new(&races, &RaceModel::default());
+
+        assert_eq!(
+            cells(&report.to_table())[1],
+            ["7", "12", "-", "0", "3", "12", "-"]
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "races": [{
+                    "time": 7,
+                    "record": 12,
+                    "first_win": null,
+                    "last_win": null,
+                    "ways": 0,
+                    "optimal_hold": 3,
+                    "max_distance": 12,
+                    "margin": null
+                }],
+                "product": 0
+            })
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::ops::RangeInclusive;
+
+/// Distance travelled holding the button for `hold` out of `time`, `None` if it doesn't fit in a `u128`
+pub fn distance(time: u128, hold: u128) -> Option<u128> {
+    hold.checked_mul(time - hold)
+}
+
+fn beats(time: u128, record: u128, hold: u128) -> bool {
+    // A distance too big for a `u128` beats every record
+    distance(time, hold).is_none_or(|distance| distance > record)
+}
+
+/// Hold times which beat `record`, `None` if no hold time does
+///
+/// The bounds are the roots of `hold * (time - hold) = record`, found with integer square roots so
+/// there is no floating point rounding.
+pub fn winning_holds(time: u128, record: u128) -> Option<RangeInclusive<u128>> {
+    // Distances peak at half the time and are symmetric around it
+    let peak = time / 2;
+
+    if !beats(time, record, peak) {
+        return None;
+    }
+
+    let mut low = match time.checked_mul(time) {
+        // `time² >= 4 * peak distance > 4 * record`, so this can't underflow
+        Some(square) => (time - (square - 4 * record).isqrt()) / 2,
+        // Too big to square, search for the root instead
+        None => {
+            let (mut low, mut high) = (0, peak);
+
+            while low < high {
+                let middle = low + (high - low) / 2;
+
+                if beats(time, record, middle) {
+                    high = middle;
+                } else {
+                    low = middle + 1;
+                }
+            }
+
+            low
+        }
+    };
+
+    // The root is rounded down, so the first winning hold is at most one step away
+    while !beats(time, record, low) {
+        low += 1;
+    }
+    while low > 0 && beats(time, record, low - 1) {
+        low -= 1;
+    }
+
+    Some(low..=time - low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(time: u128, record: u128) -> Option<RangeInclusive<u128>> {
+        let mut winning = (0..=time).filter(|&hold| hold * (time - hold) > record);
+        let first = winning.next()?;
+
+        Some(first..=winning.next_back().unwrap_or(first))
+    }
+
+    /// Checks `holds` are exactly where the distance goes past `record`, without scanning
+    fn assert_bounds(time: u128, record: u128, holds: RangeInclusive<u128>) {
+        let (&first, &last) = (holds.start(), holds.end());
+
+        assert!(beats(time, record, first) && beats(time, record, last));
+        assert!(first == 0 || !beats(time, record, first - 1));
+        assert!(last == time || !beats(time, record, last + 1));
+    }
+
+    #[test]
+    fn sample() {
+        assert_eq!(winning_holds(7, 9), Some(2..=5));
+        assert_eq!(winning_holds(15, 40), Some(4..=11));
+        assert_eq!(winning_holds(30, 200), Some(11..=19));
+        assert_eq!(winning_holds(71530, 940200), Some(14..=71516));
+    }
+
+    #[test]
+    fn matches_scan() {
+        for time in 0..60 {
+            for record in 0..time * time / 4 + 3 {
+                assert_eq!(
+                    winning_holds(time, record),
+                    scan(time, record),
+                    "{time} {record}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn u128_races() {
+        let races = [
+            // Squared time fits, solved with the square root
+            (62737565, 644102312401023),
+            ((1 << 63) + 12345, (1 << 124) + 6789),
+            // Squared time doesn't fit, solved by searching
+            (1 << 70, (1 << 100) + 12345),
+            (u128::MAX, u128::MAX),
+            (u128::MAX - 1, 12345),
+        ];
+
+        for (time, record) in races {
+            assert_bounds(time, record, winning_holds(time, record).unwrap());
+        }
+
+        assert_eq!(winning_holds(1 << 64, u128::MAX), None);
+    }
+}